@@ -3,7 +3,12 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use thiserror::Error;
+use tokio::io::{AsyncRead, ReadBuf};
 
 #[derive(Debug, Error)]
 pub enum PrivacyError {
@@ -14,9 +19,10 @@ pub enum PrivacyError {
 }
 
 /// Sensitivity level for classified data
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SensitivityLevel {
     Public,
+    #[default]
     Normal,
     Sensitive,
     HighlySensitive,
@@ -35,12 +41,6 @@ impl std::fmt::Display for SensitivityLevel {
     }
 }
 
-impl Default for SensitivityLevel {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl PartialOrd for SensitivityLevel {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -68,14 +68,77 @@ impl Ord for SensitivityLevel {
 }
 
 /// Classification rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ClassificationRule {
     pub name: String,
     pub pattern: String,
     pub level: SensitivityLevel,
     pub description: String,
+    /// Optional mask template used by [`RedactionStrategy::Mask`], e.g.
+    /// `****-****-****-$last4`. `$name`, `${name}` and `$1` reference capture
+    /// groups of `pattern`; `$$` is a literal `$`. Without a template the
+    /// match is fully masked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// Base confidence (0.0–1.0) of a match, reflecting how specific the
+    /// pattern is. Defaults to [`DEFAULT_RULE_CONFIDENCE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Words or phrases that raise confidence when they appear, as whole
+    /// words, shortly before a match (e.g. `"ssn"`). Matched case-insensitively.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cues: Vec<String>,
+    /// Optional check of the matched text that raises confidence when it
+    /// passes and lowers it when it fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<MatchValidator>,
+}
+
+/// Structural check applied to a rule's matches when scoring confidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchValidator {
+    /// Luhn checksum over the digits, as used by payment card numbers.
+    Luhn,
 }
 
+impl MatchValidator {
+    /// Whether `matched` passes the check.
+    pub fn validate(self, matched: &str) -> bool {
+        match self {
+            Self::Luhn => luhn_valid(matched),
+        }
+    }
+}
+
+impl ClassificationRule {
+    /// Create a rule with no replacement template, the default confidence,
+    /// no cues and no validator.
+    ///
+    /// Prefer this over a struct literal so new optional fields do not break
+    /// callers; set the public fields afterwards or use struct update syntax.
+    pub fn new(
+        name: impl Into<String>,
+        pattern: impl Into<String>,
+        level: SensitivityLevel,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+            level,
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Base confidence for rules that do not set one.
+pub const DEFAULT_RULE_CONFIDENCE: f32 = 0.7;
+
+/// How far before a match to look for cue words such as "ssn" or "card".
+const CONTEXT_WINDOW: usize = 32;
+
 /// A single match found during classification
 #[derive(Debug, Clone)]
 pub struct ClassificationMatch {
@@ -84,6 +147,9 @@ pub struct ClassificationMatch {
     pub start: usize,
     pub end: usize,
     pub matched_text: String,
+    /// Likelihood (0.0–1.0) that the match is real PII, from the rule's base
+    /// confidence adjusted by validation and surrounding context.
+    pub confidence: f32,
 }
 
 /// PII match (alias for ClassificationMatch for compatibility)
@@ -107,10 +173,20 @@ pub enum RedactionStrategy {
 
 /// Regex-based classifier
 pub struct RegexClassifier {
-    rules: Vec<(String, Regex, SensitivityLevel)>,
+    rules: Vec<CompiledRule>,
     default_level: SensitivityLevel,
 }
 
+struct CompiledRule {
+    name: String,
+    regex: Regex,
+    level: SensitivityLevel,
+    replacement: Option<String>,
+    confidence: f32,
+    cues: Vec<String>,
+    validator: Option<MatchValidator>,
+}
+
 impl RegexClassifier {
     /// Create a new classifier with the given rules
     pub fn new(
@@ -122,7 +198,23 @@ impl RegexClassifier {
             .map(|rule| {
                 let regex = Regex::new(&rule.pattern)
                     .map_err(|e| PrivacyError::InvalidPattern(format!("{}: {}", rule.name, e)))?;
-                Ok((rule.name.clone(), regex, rule.level))
+                if let Some(template) = &rule.replacement {
+                    validate_replacement(&regex, template).map_err(|e| {
+                        PrivacyError::InvalidPattern(format!("{}: {}", rule.name, e))
+                    })?;
+                }
+                Ok(CompiledRule {
+                    name: rule.name.clone(),
+                    regex,
+                    level: rule.level,
+                    replacement: rule.replacement.clone(),
+                    confidence: rule
+                        .confidence
+                        .unwrap_or(DEFAULT_RULE_CONFIDENCE)
+                        .clamp(0.0, 1.0),
+                    cues: rule.cues.iter().map(|cue| cue.to_lowercase()).collect(),
+                    validator: rule.validator,
+                })
             })
             .collect::<Result<Vec<_>, PrivacyError>>()?;
 
@@ -134,23 +226,21 @@ impl RegexClassifier {
 
     /// Classify text and return matches
     pub fn classify(&self, text: &str) -> ClassificationResult {
-        let mut matches = Vec::new();
-        let mut overall_level = self.default_level;
-
-        for (rule_name, regex, level) in &self.rules {
-            for mat in regex.find_iter(text) {
-                matches.push(ClassificationMatch {
-                    rule_name: rule_name.clone(),
-                    level: *level,
-                    start: mat.start(),
-                    end: mat.end(),
-                    matched_text: mat.as_str().to_string(),
-                });
-                if *level > overall_level {
-                    overall_level = *level;
-                }
-            }
-        }
+        self.classify_with_threshold(text, 0.0)
+    }
+
+    /// Classify text, dropping matches whose confidence is below
+    /// `min_confidence`. The overall level only reflects kept matches.
+    pub fn classify_with_threshold(&self, text: &str, min_confidence: f32) -> ClassificationResult {
+        let matches: Vec<ClassificationMatch> = self
+            .rule_matches(text, min_confidence)
+            .into_iter()
+            .map(|(_, mat)| mat)
+            .collect();
+        let overall_level = matches
+            .iter()
+            .map(|mat| mat.level)
+            .fold(self.default_level, std::cmp::max);
 
         let requires_tee = overall_level >= SensitivityLevel::Sensitive;
 
@@ -161,23 +251,88 @@ impl RegexClassifier {
         }
     }
 
+    /// All matches at or above `min_confidence`, each paired with the index
+    /// of the rule that produced it. Rule names need not be unique, so the
+    /// index is what ties a match back to its template.
+    fn rule_matches(&self, text: &str, min_confidence: f32) -> Vec<(usize, ClassificationMatch)> {
+        let mut matches = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            for mat in rule.regex.find_iter(text) {
+                let confidence = match_confidence(rule, text, mat.start(), mat.as_str());
+                if confidence < min_confidence {
+                    continue;
+                }
+                matches.push((
+                    index,
+                    ClassificationMatch {
+                        rule_name: rule.name.clone(),
+                        level: rule.level,
+                        start: mat.start(),
+                        end: mat.end(),
+                        matched_text: mat.as_str().to_string(),
+                        confidence,
+                    },
+                ));
+            }
+        }
+        matches
+    }
+
     /// Redact sensitive data in text
     pub fn redact(&self, text: &str, strategy: RedactionStrategy) -> String {
         let mut result = text.to_string();
-        let classification = self.classify(text);
 
         // Sort matches by start position in reverse order to avoid offset issues
-        let mut matches = classification.matches;
-        matches.sort_by(|a, b| b.start.cmp(&a.start));
+        let mut matches = self.rule_matches(text, 0.0);
+        matches.sort_by_key(|(_, m)| std::cmp::Reverse(m.start));
 
-        for mat in matches {
-            let redacted = redact_text(&mat.matched_text, &mat.rule_name, strategy);
+        for (rule_index, mat) in matches {
+            let redacted = self.render_match(text, rule_index, &mat, strategy);
             result.replace_range(mat.start..mat.end, &redacted);
         }
 
         result
     }
 
+    /// Render the redacted form of one match found in `text` by rule
+    /// `rule_index`.
+    fn render_match(
+        &self,
+        text: &str,
+        rule_index: usize,
+        mat: &ClassificationMatch,
+        strategy: RedactionStrategy,
+    ) -> String {
+        match strategy {
+            RedactionStrategy::Mask => self.expand_replacement(text, rule_index, mat),
+            _ => None,
+        }
+        .unwrap_or_else(|| redact_text(&mat.matched_text, &mat.rule_name, strategy))
+    }
+
+    /// Render the rule's replacement template for one match, if it has one.
+    ///
+    /// Captures are re-run with the exact rule that matched, starting at the
+    /// match, and must cover the same span; the full text is kept as context
+    /// so anchors such as `\b` behave as they did when matching.
+    fn expand_replacement(
+        &self,
+        text: &str,
+        rule_index: usize,
+        mat: &ClassificationMatch,
+    ) -> Option<String> {
+        let rule = self.rules.get(rule_index)?;
+        let template = rule.replacement.as_deref()?;
+        let captures = rule.regex.captures_at(text, mat.start)?;
+        let whole = captures.get(0)?;
+        if (whole.start(), whole.end()) != (mat.start, mat.end) {
+            return None;
+        }
+        let mut rendered = String::new();
+        captures.expand(template, &mut rendered);
+        Some(rendered)
+    }
+
     /// Check if text contains sensitive data
     pub fn contains_sensitive(&self, text: &str) -> bool {
         self.classify(text).overall_level >= SensitivityLevel::Sensitive
@@ -189,6 +344,108 @@ impl RegexClassifier {
     }
 }
 
+/// Score one match: the rule's base confidence, adjusted by the rule's
+/// validator and by its cue words just before the match.
+fn match_confidence(rule: &CompiledRule, text: &str, start: usize, matched: &str) -> f32 {
+    let mut confidence = rule.confidence;
+
+    if let Some(validator) = rule.validator {
+        confidence += if validator.validate(matched) {
+            0.1
+        } else {
+            -0.35
+        };
+    }
+
+    if rule.cues.is_empty() {
+        return confidence.clamp(0.0, 1.0);
+    }
+    let mut window_start = start.saturating_sub(CONTEXT_WINDOW);
+    while !text.is_char_boundary(window_start) {
+        window_start -= 1;
+    }
+    let context = text[window_start..start].to_lowercase();
+    if rule.cues.iter().any(|cue| contains_word(&context, cue)) {
+        confidence += 0.1;
+    }
+
+    confidence.clamp(0.0, 1.0)
+}
+
+/// Whether `word` occurs in `text` with no letter or digit on either side, so
+/// `"tel"` matches `"tel: "` but not `"hotel"`.
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_word_char = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    text.match_indices(word).any(|(index, _)| {
+        !is_word_char(text[..index].chars().next_back())
+            && !is_word_char(text[index + word.len()..].chars().next())
+    })
+}
+
+/// Luhn checksum over the digits of a card number.
+fn luhn_valid(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() < 12 {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Check that every capture group a replacement template references exists.
+fn validate_replacement(regex: &Regex, template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(pos) = rest.find('$') {
+        rest = &rest[pos + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            rest = after;
+            continue;
+        }
+        let (name, after) = match rest.strip_prefix('{') {
+            Some(braced) => braced
+                .split_once('}')
+                .ok_or_else(|| format!("unclosed group reference in '{}'", template))?,
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        if name.is_empty() {
+            return Err(format!("empty group reference in '{}'", template));
+        }
+        let known = match name.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => regex.capture_names().flatten().any(|group| group == name),
+        };
+        if !known {
+            return Err(format!(
+                "unknown capture group '{}' in '{}'",
+                name, template
+            ));
+        }
+        rest = after;
+    }
+    Ok(())
+}
+
 /// Redact text based on rule name and strategy
 pub fn redact_text(text: &str, rule_name: &str, strategy: RedactionStrategy) -> String {
     match strategy {
@@ -227,30 +484,69 @@ pub fn default_classification_rules() -> Vec<ClassificationRule> {
             pattern: r"\b\d{4}[-\s]?\d{4}[-\s]?\d{4}[-\s]?\d{4}\b".to_string(),
             level: SensitivityLevel::HighlySensitive,
             description: "Credit card number".to_string(),
+            replacement: None,
+            confidence: Some(0.85),
+            cues: vec![
+                "card".to_string(),
+                "visa".to_string(),
+                "mastercard".to_string(),
+                "amex".to_string(),
+            ],
+            validator: Some(MatchValidator::Luhn),
         },
         ClassificationRule {
             name: "ssn".to_string(),
             pattern: r"\b\d{3}-\d{2}-\d{4}\b".to_string(),
             level: SensitivityLevel::HighlySensitive,
             description: "Social Security Number".to_string(),
+            replacement: None,
+            confidence: Some(0.9),
+            cues: vec!["ssn".to_string(), "social security".to_string()],
+            validator: None,
         },
         ClassificationRule {
             name: "email".to_string(),
             pattern: r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b".to_string(),
             level: SensitivityLevel::Sensitive,
             description: "Email address".to_string(),
+            replacement: None,
+            confidence: Some(0.8),
+            cues: vec![
+                "email".to_string(),
+                "e-mail".to_string(),
+                "mail".to_string(),
+            ],
+            validator: None,
         },
         ClassificationRule {
             name: "phone".to_string(),
             pattern: r"\b\d{3}[-.\s]?\d{3}[-.\s]?\d{4}\b".to_string(),
             level: SensitivityLevel::Sensitive,
             description: "Phone number".to_string(),
+            replacement: None,
+            confidence: Some(0.5),
+            cues: vec![
+                "phone".to_string(),
+                "tel".to_string(),
+                "mobile".to_string(),
+                "call".to_string(),
+            ],
+            validator: None,
         },
         ClassificationRule {
             name: "api_key".to_string(),
             pattern: r"\b[A-Za-z0-9_-]{32,}\b".to_string(),
             level: SensitivityLevel::Critical,
             description: "API key or token".to_string(),
+            replacement: None,
+            confidence: Some(0.4),
+            cues: vec![
+                "key".to_string(),
+                "token".to_string(),
+                "secret".to_string(),
+                "bearer".to_string(),
+            ],
+            validator: None,
         },
     ]
 }
@@ -368,6 +664,352 @@ impl KeywordMatcher {
     }
 }
 
+/// Default number of bytes [`RedactingReader`] scans per step.
+pub const DEFAULT_REDACTION_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Default overlap [`RedactingReader`] holds back between steps, sized for the
+/// longest match the default rules are expected to produce.
+pub const DEFAULT_REDACTION_OVERLAP: usize = 1024;
+
+/// Streaming redaction over any `AsyncRead` source.
+///
+/// Input is decoded as UTF-8 (invalid sequences become U+FFFD) and scanned in
+/// steps of about `chunk_size` bytes. Each step scans only text not yet
+/// emitted, with the preceding character kept as context so `\b` sees the
+/// real boundary. Text within `overlap` bytes of the end of the buffer is held
+/// back, and a match that may still be growing there is deferred whole, so
+/// matches straddling a read boundary are redacted as in
+/// [`RegexClassifier::redact`].
+///
+/// Buffering is bounded by roughly `2 * (chunk_size + overlap)` bytes. A match
+/// longer than `overlap` that is still open at the end of the buffer, such as
+/// a long token run for the unbounded `api_key` rule, is not buffered: its
+/// length is counted until it ends and it is then replaced by one placeholder
+/// (`[REDACTED]` for [`RedactionStrategy::Mask`], nothing for
+/// [`RedactionStrategy::Remove`], `[HASH:<len>]` for [`RedactionStrategy::Hash`]),
+/// since no template can be rendered for it. Its continuation is found by
+/// re-matching the rule from the retained tail, which suits run-like patterns.
+pub struct RedactingReader<R> {
+    inner: R,
+    classifier: Arc<RegexClassifier>,
+    strategy: RedactionStrategy,
+    chunk_size: usize,
+    overlap: usize,
+    /// Bytes of an incomplete UTF-8 sequence awaiting the next read.
+    partial: Vec<u8>,
+    /// Decoded text. The first `settled` bytes were already emitted (or
+    /// counted into `open`) and are kept only as context.
+    pending: String,
+    settled: usize,
+    /// Over-long match still running at the end of `pending`.
+    open: Option<OpenMatch>,
+    /// Redacted output not yet handed to the caller.
+    output: Vec<u8>,
+    output_pos: usize,
+    eof: bool,
+}
+
+/// A match that outgrew the buffer; `pending` starts with its tail.
+struct OpenMatch {
+    rule_index: usize,
+    /// Bytes of the match consumed so far, including the retained tail.
+    len: usize,
+}
+
+impl<R: AsyncRead + Unpin> RedactingReader<R> {
+    /// Wrap `inner`, redacting matches of `classifier` with `strategy`.
+    pub fn new(inner: R, classifier: Arc<RegexClassifier>, strategy: RedactionStrategy) -> Self {
+        Self {
+            inner,
+            classifier,
+            strategy,
+            chunk_size: DEFAULT_REDACTION_CHUNK_SIZE,
+            overlap: DEFAULT_REDACTION_OVERLAP,
+            partial: Vec::new(),
+            pending: String::new(),
+            settled: 0,
+            open: None,
+            output: Vec::new(),
+            output_pos: 0,
+            eof: false,
+        }
+    }
+
+    /// Set how many bytes are scanned per step.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set how many trailing bytes are held back and rescanned; this should
+    /// cover the longest match the rules can produce.
+    pub fn with_overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Consume the adapter and return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Decode freshly read bytes onto `pending`.
+    fn decode(&mut self, bytes: &[u8]) {
+        self.partial.extend_from_slice(bytes);
+        let mut rest = self.partial.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    self.pending.push_str(valid);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, after) = rest.split_at(error.valid_up_to());
+                    // `valid_up_to` bytes always decode.
+                    self.pending
+                        .push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match error.error_len() {
+                        Some(len) => {
+                            self.pending.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        None if self.eof => {
+                            self.pending.push(char::REPLACEMENT_CHARACTER);
+                            rest = &[];
+                            break;
+                        }
+                        None => {
+                            rest = after;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.partial = rest.to_vec();
+    }
+
+    /// Bytes decoded but not yet emitted or counted.
+    fn unsettled(&self) -> usize {
+        self.pending.len() - self.settled
+    }
+
+    /// Whether a step is due: enough text is buffered, or input has ended
+    /// with text or an open match left over.
+    fn step_due(&self) -> bool {
+        if self.eof {
+            self.unsettled() > 0 || self.open.is_some()
+        } else {
+            self.unsettled() >= self.chunk_size + self.overlap
+        }
+    }
+
+    /// Advance by one step, always settling some text or closing a match.
+    fn step(&mut self) {
+        if self.open.is_some() {
+            self.continue_open_match();
+        } else {
+            self.scan();
+        }
+    }
+
+    /// Redact and emit unsettled text up to the held-back window.
+    fn scan(&mut self) {
+        let text = self.pending.as_str();
+        let from = self.settled;
+        let limit = if self.eof {
+            text.len()
+        } else {
+            floor_char_boundary(text, text.len().saturating_sub(self.overlap)).max(from)
+        };
+
+        let mut candidates = Vec::new();
+        for (rule_index, rule) in self.classifier.rules.iter().enumerate() {
+            let mut at = from;
+            while let Some(mat) = rule.regex.find_at(text, at) {
+                if mat.start() >= limit {
+                    break;
+                }
+                candidates.push((rule_index, mat.start(), mat.end()));
+                at = if mat.end() > mat.start() {
+                    mat.end()
+                } else {
+                    match text[mat.end()..].chars().next() {
+                        Some(c) => mat.end() + c.len_utf8(),
+                        None => break,
+                    }
+                };
+            }
+        }
+        candidates.sort_by_key(|&(_, start, end)| (start, std::cmp::Reverse(end)));
+
+        // Keep non-overlapping matches, leftmost and longest first. A match
+        // ending inside the buffer is final; one running to the buffer end
+        // may still grow, so it is deferred or, if already longer than the
+        // overlap, opened.
+        let mut cut = limit;
+        let mut covered = from;
+        let mut selected = Vec::new();
+        let mut opened = None;
+        for (rule_index, start, end) in candidates {
+            if start < covered {
+                continue;
+            }
+            if end == text.len() && !self.eof {
+                if end - start > self.overlap {
+                    opened = Some((rule_index, start));
+                }
+                cut = start;
+                break;
+            }
+            covered = end;
+            cut = cut.max(end);
+            selected.push((rule_index, start, end));
+        }
+
+        let mut emitted = String::with_capacity(cut - from);
+        let mut pos = from;
+        for &(rule_index, start, end) in &selected {
+            emitted.push_str(&text[pos..start]);
+            let mat = ClassificationMatch {
+                rule_name: self.classifier.rules[rule_index].name.clone(),
+                level: self.classifier.rules[rule_index].level,
+                start,
+                end,
+                matched_text: text[start..end].to_string(),
+                confidence: 1.0,
+            };
+            emitted.push_str(
+                &self
+                    .classifier
+                    .render_match(text, rule_index, &mat, self.strategy),
+            );
+            pos = end;
+        }
+        emitted.push_str(&text[pos..cut]);
+        self.emit(emitted);
+
+        match opened {
+            Some((rule_index, start)) => {
+                self.open = Some(OpenMatch {
+                    rule_index,
+                    len: self.pending.len() - start,
+                });
+                self.keep_open_tail();
+            }
+            None => self.settle(cut),
+        }
+    }
+
+    /// Extend the open match over newly buffered text, closing it once it
+    /// stops matching or input ends.
+    fn continue_open_match(&mut self) {
+        let Some(open) = self.open.as_mut() else {
+            return;
+        };
+        // `pending` starts with the match's own tail, so anchoring the rule
+        // there finds how far the run continues.
+        let regex = &self.classifier.rules[open.rule_index].regex;
+        let end = match regex.find_at(&self.pending, 0) {
+            Some(mat) if mat.start() == 0 => mat.end().max(self.settled),
+            _ => self.settled,
+        };
+        open.len += end - self.settled;
+
+        if end == self.pending.len() && !self.eof {
+            self.keep_open_tail();
+            return;
+        }
+        let Some(open) = self.open.take() else {
+            return;
+        };
+        let placeholder = match self.strategy {
+            RedactionStrategy::Mask => "[REDACTED]".to_string(),
+            RedactionStrategy::Remove => String::new(),
+            RedactionStrategy::Hash => format!("[HASH:{}]", open.len),
+        };
+        self.emit(placeholder);
+        self.settle(end);
+    }
+
+    /// Keep only the last `overlap` bytes (at least one character) of the
+    /// open match, all of them already counted.
+    fn keep_open_tail(&mut self) {
+        let keep = self.overlap.max(1).min(self.pending.len());
+        let mut start = self.pending.len() - keep;
+        while !self.pending.is_char_boundary(start) {
+            start -= 1;
+        }
+        self.pending.drain(..start);
+        self.settled = self.pending.len();
+    }
+
+    /// Mark everything before `cut` as emitted, keeping one character of it
+    /// as context for the next scan.
+    fn settle(&mut self, cut: usize) {
+        let context = self.pending[..cut]
+            .chars()
+            .next_back()
+            .map_or(0, char::len_utf8);
+        self.pending.drain(..cut - context);
+        self.settled = context;
+    }
+
+    fn emit(&mut self, text: String) {
+        self.output = text.into_bytes();
+        self.output_pos = 0;
+    }
+}
+
+/// Largest char boundary of `text` at or below `index`.
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for RedactingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.output_pos < this.output.len() {
+                let available = &this.output[this.output_pos..];
+                let n = available.len().min(buf.remaining());
+                buf.put_slice(&available[..n]);
+                this.output_pos += n;
+                return Poll::Ready(Ok(()));
+            }
+            if this.step_due() {
+                this.step();
+                continue;
+            }
+            if this.eof {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut chunk = vec![0u8; this.chunk_size];
+            let mut read_buf = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut this.inner).poll_read(cx, &mut read_buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Ready(Ok(())) => {
+                    let filled = read_buf.filled().len();
+                    if filled == 0 {
+                        this.eof = true;
+                    }
+                    this.decode(&chunk[..filled]);
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,6 +1069,355 @@ mod tests {
         assert_eq!(redacted, "****@example.com");
     }
 
+    #[test]
+    fn test_classification_rule_new_leaves_optional_fields_unset() {
+        let rule = ClassificationRule::new("id", r"ID-\d+", SensitivityLevel::Sensitive, "ID");
+        assert_eq!(rule.name, "id");
+        assert!(rule.replacement.is_none() && rule.confidence.is_none());
+        assert!(rule.cues.is_empty() && rule.validator.is_none());
+
+        let parsed: ClassificationRule = serde_json::from_str(
+            r#"{"name":"id","pattern":"ID-\\d+","level":"Sensitive","description":"ID"}"#,
+        )
+        .unwrap();
+        assert!(parsed.replacement.is_none() && parsed.cues.is_empty());
+    }
+
+    #[test]
+    fn test_redact_with_capture_template_preserves_suffix() {
+        let rules = vec![ClassificationRule {
+            replacement: Some("****-****-****-$last4".to_string()),
+            ..ClassificationRule::new(
+                "card",
+                r"\b\d{4}[-\s]?\d{4}[-\s]?\d{4}[-\s]?(?P<last4>\d{4})\b",
+                SensitivityLevel::HighlySensitive,
+                "Card with visible suffix",
+            )
+        }];
+        let classifier = RegexClassifier::new(&rules, SensitivityLevel::Normal).unwrap();
+
+        assert_eq!(
+            classifier.redact(
+                "Pay with 4111 1111 1111 4242 today",
+                RedactionStrategy::Mask
+            ),
+            "Pay with ****-****-****-4242 today"
+        );
+        // Other strategies ignore the template.
+        assert_eq!(
+            classifier.redact("4111 1111 1111 4242", RedactionStrategy::Remove),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_redact_without_template_fully_masks() {
+        let rules = vec![ClassificationRule::new(
+            "employee_id",
+            r"EMP-\d{6}",
+            SensitivityLevel::Sensitive,
+            "Employee ID",
+        )];
+        let classifier = RegexClassifier::new(&rules, SensitivityLevel::Normal).unwrap();
+
+        assert_eq!(
+            classifier.redact("id EMP-123456", RedactionStrategy::Mask),
+            "id [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_replacement_template_uses_the_rule_that_matched() {
+        let rule = |pattern: &str, replacement: Option<&str>| ClassificationRule {
+            replacement: replacement.map(str::to_string),
+            ..ClassificationRule::new("card", pattern, SensitivityLevel::HighlySensitive, "Card")
+        };
+        let rules = vec![
+            rule(r"(?P<last4>\d{4})-END", Some("****$last4")),
+            rule(r"\d{4}-OTHER", None),
+        ];
+        let classifier = RegexClassifier::new(&rules, SensitivityLevel::Normal).unwrap();
+
+        assert_eq!(
+            classifier.redact("1111-OTHER 2222-END", RedactionStrategy::Mask),
+            "[REDACTED] ****2222"
+        );
+    }
+
+    #[test]
+    fn test_replacement_template_must_reference_known_groups() {
+        let rule = |replacement: &str| ClassificationRule {
+            replacement: Some(replacement.to_string()),
+            ..ClassificationRule::new(
+                "token",
+                r"tok_(?P<tail>\w{4})",
+                SensitivityLevel::Critical,
+                "Token",
+            )
+        };
+
+        assert!(RegexClassifier::new(&[rule("tok_****${tail}")], SensitivityLevel::Normal).is_ok());
+        assert!(RegexClassifier::new(&[rule("$$1 $1")], SensitivityLevel::Normal).is_ok());
+        assert!(matches!(
+            RegexClassifier::new(&[rule("$last4")], SensitivityLevel::Normal),
+            Err(PrivacyError::InvalidPattern(_))
+        ));
+        assert!(RegexClassifier::new(&[rule("$2")], SensitivityLevel::Normal).is_err());
+        assert!(RegexClassifier::new(&[rule("${tail")], SensitivityLevel::Normal).is_err());
+    }
+
+    /// Reader that yields its input in fixed pieces, one per read.
+    struct PiecewiseReader {
+        pieces: std::collections::VecDeque<Vec<u8>>,
+    }
+
+    impl PiecewiseReader {
+        fn new(input: &[u8], splits: &[usize]) -> Self {
+            let mut pieces = std::collections::VecDeque::new();
+            let mut start = 0;
+            for &end in splits.iter().chain(std::iter::once(&input.len())) {
+                pieces.push_back(input[start..end].to_vec());
+                start = end;
+            }
+            Self { pieces }
+        }
+    }
+
+    impl AsyncRead for PiecewiseReader {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            if let Some(mut piece) = self.pieces.pop_front() {
+                let rest = piece.split_off(piece.len().min(buf.remaining()));
+                buf.put_slice(&piece);
+                if !rest.is_empty() {
+                    self.pieces.push_front(rest);
+                }
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    async fn redact_stream(reader: PiecewiseReader, chunk_size: usize, overlap: usize) -> String {
+        use tokio::io::AsyncReadExt;
+
+        let classifier = Arc::new(
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap(),
+        );
+        let mut redacted = String::new();
+        RedactingReader::new(reader, classifier, RedactionStrategy::Mask)
+            .with_chunk_size(chunk_size)
+            .with_overlap(overlap)
+            .read_to_string(&mut redacted)
+            .await
+            .unwrap();
+        redacted
+    }
+
+    #[tokio::test]
+    async fn test_streaming_redaction_catches_match_across_chunks() {
+        let text = "log line one\nSSN 123-45-6789 and mail alice@example.com\ntrailer";
+        let split = text.find("45-").unwrap();
+        let reader = PiecewiseReader::new(text.as_bytes(), &[split]);
+
+        let redacted = redact_stream(reader, split, 32).await;
+
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        assert_eq!(redacted, classifier.redact(text, RedactionStrategy::Mask));
+        assert!(redacted.contains("***-**-****"));
+        assert!(!redacted.contains("6789"));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_redaction_handles_split_utf8_and_tiny_chunks() {
+        let text = "héllo — card 4111 1111 1111 1111 ✓";
+        let bytes = text.as_bytes();
+        let splits: Vec<usize> = (1..bytes.len()).step_by(3).collect();
+        let reader = PiecewiseReader::new(bytes, &splits);
+
+        let redacted = redact_stream(reader, 4, 32).await;
+
+        assert_eq!(redacted, "héllo — card ****-****-****-1111 ✓");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_redaction_matches_whole_buffer_redaction_at_every_cut() {
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        // "x555-123-4567" has no word boundary before the digits, so it must
+        // stay unredacted even when a step starts right before the "5".
+        let text = "ref x555-123-4567, call 555-123-4567 or SSN 123-45-6789.";
+        let expected = classifier.redact(text, RedactionStrategy::Mask);
+        assert!(expected.contains("x555-123-4567"));
+
+        for chunk_size in 1..=24 {
+            let reader = PiecewiseReader::new(text.as_bytes(), &[]);
+            let redacted = redact_stream(reader, chunk_size, 16).await;
+            assert_eq!(redacted, expected, "chunk size {chunk_size}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_redaction_bounds_buffer_for_long_matches() {
+        use tokio::io::AsyncReadExt;
+
+        let run = "a".repeat(200_000);
+        let text = format!("start {run} end 123-45-6789");
+        let classifier = Arc::new(
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap(),
+        );
+        let (chunk_size, overlap) = (1024, 64);
+        let mut reader = RedactingReader::new(
+            PiecewiseReader::new(text.as_bytes(), &[]),
+            classifier,
+            RedactionStrategy::Hash,
+        )
+        .with_chunk_size(chunk_size)
+        .with_overlap(overlap);
+
+        let mut redacted = Vec::new();
+        let mut buf = [0u8; 256];
+        let mut max_pending = 0;
+        loop {
+            let n = reader.read(&mut buf).await.unwrap();
+            max_pending = max_pending.max(reader.pending.len());
+            if n == 0 {
+                break;
+            }
+            redacted.extend_from_slice(&buf[..n]);
+        }
+
+        assert!(
+            max_pending <= 2 * (chunk_size + overlap),
+            "buffered {max_pending} bytes"
+        );
+        assert_eq!(
+            String::from_utf8(redacted).unwrap(),
+            "start [HASH:200000] end [HASH:11]"
+        );
+    }
+
+    #[test]
+    fn test_confidence_orders_pattern_types() {
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        let confidence = |text: &str, rule: &str| {
+            classifier
+                .classify(text)
+                .matches
+                .into_iter()
+                .find(|m| m.rule_name == rule)
+                .unwrap()
+                .confidence
+        };
+
+        let card = confidence("4111 1111 1111 1111", "credit_card");
+        let ssn = confidence("123-45-6789", "ssn");
+        let email = confidence("alice@example.com", "email");
+        let phone = confidence("555-123-4567", "phone");
+
+        assert!(card > email && ssn > email, "{card} {ssn} {email}");
+        assert!(email > phone, "{email} {phone}");
+        assert!((0.0..=1.0).contains(&card) && (0.0..=1.0).contains(&phone));
+    }
+
+    #[test]
+    fn test_confidence_uses_checksum_and_context() {
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        let confidence = |text: &str| classifier.classify(text).matches[0].confidence;
+
+        assert!(confidence("4111 1111 1111 1111") > confidence("4111 1111 1111 1112"));
+        assert!(confidence("call me at 555-123-4567") > confidence("ref 555-123-4567"));
+        assert!(confidence("Tel: 555-123-4567") > confidence("ref 555-123-4567"));
+    }
+
+    #[test]
+    fn test_confidence_cues_match_whole_words_only() {
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        let confidence = |text: &str| classifier.classify(text).matches[0].confidence;
+        let token = "abcdefghijklmnopqrstuvwxyz0123456789";
+
+        let phone = confidence("ref 555-123-4567");
+        assert_eq!(confidence("hotel 555-123-4567"), phone);
+        assert_eq!(confidence("recall 555-123-4567"), phone);
+
+        let key = confidence(&format!("ref {token}"));
+        assert_eq!(confidence(&format!("monkey {token}")), key);
+        assert!(confidence(&format!("api key: {token}")) > key);
+    }
+
+    #[test]
+    fn test_confidence_scoring_comes_from_rule_fields() {
+        let rule = |name: &str, cues: Vec<String>, validator| ClassificationRule {
+            confidence: Some(0.5),
+            cues,
+            validator,
+            ..ClassificationRule::new(
+                name,
+                r"\b\d{4} \d{4} \d{4} \d{4}\b",
+                SensitivityLevel::Sensitive,
+                "Account number",
+            )
+        };
+        let score = |rule: ClassificationRule, text: &str| {
+            RegexClassifier::new(&[rule], SensitivityLevel::Normal)
+                .unwrap()
+                .classify(text)
+                .matches[0]
+                .confidence
+        };
+        let invalid = "4111 1111 1111 1112";
+
+        // Reusing a default rule name does not inherit its cues or validator.
+        assert_eq!(score(rule("credit_card", Vec::new(), None), invalid), 0.5);
+        assert_eq!(
+            score(
+                rule("credit_card", Vec::new(), None),
+                "card 4111 1111 1111 1112"
+            ),
+            0.5
+        );
+        // A custom rule gets context and validation scoring from its fields.
+        let account = || rule("account", vec!["Account".to_string()], None);
+        assert!(score(account(), &format!("account {invalid}")) > 0.5);
+        assert!(
+            score(
+                rule("account", Vec::new(), Some(MatchValidator::Luhn)),
+                invalid
+            ) < 0.5
+        );
+    }
+
+    #[test]
+    fn test_classify_with_threshold_drops_low_confidence_matches() {
+        let classifier =
+            RegexClassifier::new(&default_classification_rules(), SensitivityLevel::Normal)
+                .unwrap();
+        let text = "order 555-123-4567";
+
+        assert_eq!(classifier.classify(text).matches.len(), 1);
+        let result = classifier.classify_with_threshold(text, 0.6);
+        assert!(result.matches.is_empty());
+        assert_eq!(result.overall_level, SensitivityLevel::Normal);
+        assert!(!result.requires_tee);
+
+        let kept = classifier.classify_with_threshold("SSN 123-45-6789", 0.6);
+        assert_eq!(kept.matches.len(), 1);
+        assert_eq!(kept.overall_level, SensitivityLevel::HighlySensitive);
+    }
+
     #[test]
     fn test_keyword_matcher() {
         let config = KeywordMatcherConfig {
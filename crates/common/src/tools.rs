@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use thiserror::Error;
 
 /// Tool definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,58 +20,127 @@ pub struct ToolResult {
     pub error: Option<String>,
 }
 
+/// Why a workspace path could not be resolved
+#[derive(Debug, Error)]
+pub enum PathResolveError {
+    /// The path to read does not exist. Displays like the historical
+    /// `resolve_path` message, which callers match on.
+    #[error("Failed to resolve path: {source}")]
+    NotFound {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The resolved path lies outside the workspace.
+    #[error("Path escapes workspace: {0}")]
+    EscapesWorkspace(String),
+    /// The path has no file name or its parent directory does not exist.
+    #[error("Invalid path or parent directory doesn't exist: {0}")]
+    InvalidParent(String),
+    /// The write target is a symlink whose destination does not exist.
+    #[error("Path is a dangling symlink: {0}")]
+    DanglingSymlink(String),
+    /// Any other filesystem failure, including an unusable workspace.
+    #[error("{context}: {source}")]
+    Io {
+        context: &'static str,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
 /// Resolve a path relative to workspace, ensuring it's within bounds
 pub fn resolve_path(workspace: &Path, path: &str) -> Result<PathBuf, String> {
-    let resolved = workspace.join(path);
+    try_resolve_path(workspace, path).map_err(|e| e.to_string())
+}
 
-    // Canonicalize both to resolve .. and symlinks
-    let canonical_workspace = workspace
-        .canonicalize()
-        .map_err(|e| format!("Failed to canonicalize workspace: {}", e))?;
-    let canonical = resolved
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+/// Resolve a path for writing (allows non-existent files)
+///
+/// See [`try_resolve_path_for_write`] for the symlink rules.
+pub fn resolve_path_for_write(workspace: &Path, path: &str) -> Result<PathBuf, String> {
+    try_resolve_path_for_write(workspace, path).map_err(|e| e.to_string())
+}
+
+/// Resolve an existing path relative to workspace, ensuring it's within bounds
+pub fn try_resolve_path(workspace: &Path, path: &str) -> Result<PathBuf, PathResolveError> {
+    let canonical_workspace = canonicalize_workspace(workspace)?;
+    let resolved = canonical_workspace.join(path);
+
+    // Canonicalize to resolve .. and symlinks
+    let canonical = resolved.canonicalize().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => PathResolveError::NotFound {
+            path: path.to_string(),
+            source: e,
+        },
+        _ => PathResolveError::Io {
+            context: "Failed to resolve path",
+            source: e,
+        },
+    })?;
 
     // Ensure the path is within workspace
     if !canonical.starts_with(&canonical_workspace) {
-        return Err(format!("Path escapes workspace: {}", path));
+        return Err(PathResolveError::EscapesWorkspace(path.to_string()));
     }
 
     Ok(canonical)
 }
 
 /// Resolve a path for writing (allows non-existent files)
-pub fn resolve_path_for_write(workspace: &Path, path: &str) -> Result<PathBuf, String> {
-    let resolved = workspace.join(path);
-
-    // Canonicalize workspace to resolve symlinks
-    let canonical_workspace = workspace
-        .canonicalize()
-        .map_err(|e| format!("Failed to canonicalize workspace: {}", e))?;
+///
+/// The workspace and the parent directory are both canonicalized, so a
+/// symlinked workspace (e.g. `/tmp` vs `/private/tmp` on macOS) compares
+/// consistently and symlinked parents cannot lead outside it. An existing
+/// symlink at the final component is followed and must also stay inside the
+/// workspace; a dangling one is rejected because writing through it would
+/// create a file at an unchecked location.
+pub fn try_resolve_path_for_write(
+    workspace: &Path,
+    path: &str,
+) -> Result<PathBuf, PathResolveError> {
+    let canonical_workspace = canonicalize_workspace(workspace)?;
+    let resolved = canonical_workspace.join(path);
 
     // Check parent directory exists and is within workspace
-    if let Some(parent) = resolved.parent() {
-        if parent.exists() {
-            let canonical = parent
-                .canonicalize()
-                .map_err(|e| format!("Failed to resolve parent: {}", e))?;
-
-            if !canonical.starts_with(&canonical_workspace) {
-                return Err(format!("Path escapes workspace: {}", path));
-            }
-
-            // Return the resolved path with the filename
-            if let Some(filename) = resolved.file_name() {
-                return Ok(canonical.join(filename));
-            }
-        }
+    let (Some(parent), Some(filename)) = (resolved.parent(), resolved.file_name()) else {
+        return Err(PathResolveError::InvalidParent(path.to_string()));
+    };
+    if !parent.exists() {
+        return Err(PathResolveError::InvalidParent(path.to_string()));
     }
+    let canonical = parent.canonicalize().map_err(|e| PathResolveError::Io {
+        context: "Failed to resolve parent",
+        source: e,
+    })?;
 
-    // If parent doesn't exist or path is invalid, return error
-    Err(format!(
-        "Invalid path or parent directory doesn't exist: {}",
-        path
-    ))
+    if !canonical.starts_with(&canonical_workspace) {
+        return Err(PathResolveError::EscapesWorkspace(path.to_string()));
+    }
+
+    let target = canonical.join(filename);
+    let is_symlink = std::fs::symlink_metadata(&target)
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false);
+    if !is_symlink {
+        return Ok(target);
+    }
+
+    let real = target
+        .canonicalize()
+        .map_err(|_| PathResolveError::DanglingSymlink(path.to_string()))?;
+    if !real.starts_with(&canonical_workspace) {
+        return Err(PathResolveError::EscapesWorkspace(path.to_string()));
+    }
+    Ok(real)
+}
+
+/// Canonicalize the workspace root, following a symlinked workspace to its
+/// real location so every later `starts_with` check compares real paths.
+fn canonicalize_workspace(workspace: &Path) -> Result<PathBuf, PathResolveError> {
+    workspace.canonicalize().map_err(|e| PathResolveError::Io {
+        context: "Failed to canonicalize workspace",
+        source: e,
+    })
 }
 
 #[cfg(test)]
@@ -101,4 +171,178 @@ mod tests {
 
         fs::remove_dir_all(&workspace).ok();
     }
+
+    #[test]
+    fn test_try_resolve_path_reports_typed_errors() {
+        let fixture = escape_fixture();
+        fs::write(fixture.outside.join("secret.txt"), "secret").unwrap();
+
+        assert!(matches!(
+            try_resolve_path(&fixture.workspace, "missing.txt"),
+            Err(PathResolveError::NotFound { path, .. }) if path == "missing.txt"
+        ));
+        assert!(matches!(
+            try_resolve_path(&fixture.workspace, "../outside/secret.txt"),
+            Err(PathResolveError::EscapesWorkspace(_))
+        ));
+        assert!(matches!(
+            try_resolve_path(&fixture.workspace.join("gone"), "."),
+            Err(PathResolveError::Io {
+                context: "Failed to canonicalize workspace",
+                ..
+            })
+        ));
+
+        // The string wrapper keeps its historical message for missing paths.
+        let message = resolve_path(&fixture.workspace, "missing.txt").unwrap_err();
+        assert_eq!(
+            message,
+            format!(
+                "Failed to resolve path: {}",
+                fixture
+                    .workspace
+                    .join("missing.txt")
+                    .canonicalize()
+                    .unwrap_err()
+            )
+        );
+    }
+
+    #[test]
+    fn test_try_resolve_path_for_write_reports_typed_errors() {
+        let fixture = escape_fixture();
+
+        assert!(matches!(
+            try_resolve_path_for_write(&fixture.workspace, "no/such/dir/file.txt"),
+            Err(PathResolveError::InvalidParent(_))
+        ));
+        assert!(matches!(
+            try_resolve_path_for_write(&fixture.workspace, "../outside/new.txt"),
+            Err(PathResolveError::EscapesWorkspace(_))
+        ));
+        assert!(matches!(
+            try_resolve_path_for_write(&fixture.workspace.join("gone"), "new.txt"),
+            Err(PathResolveError::Io { .. })
+        ));
+
+        // The string wrappers keep their messages.
+        let message = resolve_path_for_write(&fixture.workspace, "../outside/new.txt").unwrap_err();
+        assert_eq!(message, "Path escapes workspace: ../outside/new.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_try_resolve_path_for_write_reports_dangling_symlinks() {
+        let fixture = escape_fixture();
+        std::os::unix::fs::symlink(
+            fixture.outside.join("created.txt"),
+            fixture.workspace.join("dangling"),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            try_resolve_path_for_write(&fixture.workspace, "dangling"),
+            Err(PathResolveError::DanglingSymlink(_))
+        ));
+    }
+
+    /// A workspace next to an `outside` directory, both inside one temp dir.
+    struct EscapeFixture {
+        _root: tempfile::TempDir,
+        workspace: PathBuf,
+        outside: PathBuf,
+    }
+
+    fn escape_fixture() -> EscapeFixture {
+        let root = tempfile::tempdir().unwrap();
+        let workspace = root.path().join("workspace");
+        let outside = root.path().join("outside");
+        fs::create_dir_all(&workspace).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        EscapeFixture {
+            _root: root,
+            workspace,
+            outside,
+        }
+    }
+
+    fn assert_write_rejected(workspace: &Path, path: &str) {
+        let result = resolve_path_for_write(workspace, path);
+        assert!(result.is_err(), "{path} should be rejected, got {result:?}");
+    }
+
+    #[test]
+    fn test_resolve_path_for_write_rejects_traversal() {
+        let fixture = escape_fixture();
+        assert_write_rejected(&fixture.workspace, "../outside/file.txt");
+        assert_write_rejected(
+            &fixture.workspace,
+            &fixture.outside.join("file.txt").to_string_lossy(),
+        );
+
+        let inside = resolve_path_for_write(&fixture.workspace, "new.txt").unwrap();
+        assert_eq!(
+            inside,
+            fixture.workspace.canonicalize().unwrap().join("new.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_path_for_write_rejects_symlink_escapes() {
+        use std::os::unix::fs::symlink;
+
+        let fixture = escape_fixture();
+        let secret = fixture.outside.join("secret.txt");
+        fs::write(&secret, "secret").unwrap();
+
+        // Symlinked parent directory pointing outside.
+        symlink(&fixture.outside, fixture.workspace.join("linked_dir")).unwrap();
+        assert_write_rejected(&fixture.workspace, "linked_dir/new.txt");
+        assert!(resolve_path(&fixture.workspace, "linked_dir/secret.txt").is_err());
+
+        // Symlinked final component pointing at an outside file.
+        symlink(&secret, fixture.workspace.join("linked_file")).unwrap();
+        assert_write_rejected(&fixture.workspace, "linked_file");
+
+        // Dangling symlink whose target would be created outside.
+        symlink(
+            fixture.outside.join("created.txt"),
+            fixture.workspace.join("dangling"),
+        )
+        .unwrap();
+        assert_write_rejected(&fixture.workspace, "dangling");
+        assert!(!fixture.outside.join("created.txt").exists());
+
+        // A symlink that stays inside the workspace is followed.
+        fs::write(fixture.workspace.join("real.txt"), "ok").unwrap();
+        symlink("real.txt", fixture.workspace.join("alias.txt")).unwrap();
+        let resolved = resolve_path_for_write(&fixture.workspace, "alias.txt").unwrap();
+        assert_eq!(
+            resolved,
+            fixture.workspace.canonicalize().unwrap().join("real.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_workspace_resolves_consistently() {
+        use std::os::unix::fs::symlink;
+
+        let fixture = escape_fixture();
+        let link = fixture.outside.join("workspace_link");
+        symlink(&fixture.workspace, &link).unwrap();
+        fs::write(fixture.workspace.join("file.txt"), "data").unwrap();
+
+        let real = fixture.workspace.canonicalize().unwrap();
+        assert_eq!(
+            resolve_path_for_write(&link, "new.txt").unwrap(),
+            real.join("new.txt")
+        );
+        assert_eq!(
+            resolve_path(&link, "file.txt").unwrap(),
+            real.join("file.txt")
+        );
+        assert_write_rejected(&link, "../secret.txt");
+    }
 }
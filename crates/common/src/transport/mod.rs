@@ -21,7 +21,10 @@ pub mod unix;
 // Re-exports for convenience
 pub use codec::{FrameCodec, FrameReader, FrameWriter};
 pub use frame::{Frame, FrameType, MAX_PAYLOAD_SIZE};
-pub use tee::{TeeMessage, TeeRequest, TeeRequestType, TeeResponse, TeeResponseStatus};
+pub use tee::{
+    ReplayGuard, TeeMessage, TeeReplayError, TeeRequest, TeeRequestType, TeeResponse,
+    TeeResponseStatus,
+};
 #[cfg(unix)]
 pub use unix::{UnixListener, UnixTransport};
 
@@ -1,6 +1,8 @@
 //! TEE protocol types for secure communication with TEE environments.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::time::Duration;
 
 /// Message types for TEE communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +22,10 @@ pub struct TeeRequest {
     pub request_type: TeeRequestType,
     pub payload: Vec<u8>,
     pub timestamp: i64,
+    /// Single-use value checked by [`ReplayGuard`]. Requests from peers that
+    /// predate the field deserialize with an empty nonce and are rejected.
+    #[serde(default)]
+    pub nonce: String,
 }
 
 impl TeeRequest {
@@ -30,10 +36,122 @@ impl TeeRequest {
             request_type,
             payload,
             timestamp: chrono::Utc::now().timestamp_millis(),
+            nonce: uuid::Uuid::new_v4().simple().to_string(),
         }
     }
 }
 
+/// Reasons a [`ReplayGuard`] rejects a request
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TeeReplayError {
+    #[error("Request timestamp {timestamp} is outside the allowed window around {now}")]
+    StaleTimestamp { timestamp: i64, now: i64 },
+    #[error("Request nonce {0} was already used")]
+    DuplicateNonce(String),
+    #[error("Request has no nonce")]
+    MissingNonce,
+}
+
+/// Replay protection for incoming [`TeeRequest`]s.
+///
+/// A request is accepted only if its timestamp lies within `max_skew` of the
+/// local clock and its nonce has not been seen inside that window. Seen nonces
+/// are kept in a bounded cache ordered by request timestamp; when it overflows,
+/// the entry with the earliest timestamp is evicted and that timestamp becomes
+/// a low watermark, so requests at or below it are rejected instead of risking
+/// a replay of a forgotten nonce.
+#[derive(Debug)]
+pub struct ReplayGuard {
+    max_skew_ms: i64,
+    capacity: usize,
+    seen: HashSet<String>,
+    by_timestamp: BTreeSet<(i64, String)>,
+    watermark: Option<i64>,
+}
+
+impl ReplayGuard {
+    /// Default accepted clock skew between client and enclave.
+    pub const DEFAULT_MAX_SKEW: Duration = Duration::from_secs(30);
+    /// Default number of nonces remembered.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    pub fn new(max_skew: Duration, capacity: usize) -> Self {
+        Self {
+            max_skew_ms: i64::try_from(max_skew.as_millis()).unwrap_or(i64::MAX),
+            capacity: capacity.max(1),
+            seen: HashSet::new(),
+            by_timestamp: BTreeSet::new(),
+            watermark: None,
+        }
+    }
+
+    /// Check a request against the current wall clock and record its nonce.
+    pub fn check(&mut self, request: &TeeRequest) -> Result<(), TeeReplayError> {
+        self.check_at(request, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Check a request against `now` (milliseconds since the Unix epoch) and
+    /// record its nonce.
+    pub fn check_at(&mut self, request: &TeeRequest, now: i64) -> Result<(), TeeReplayError> {
+        if request.nonce.is_empty() {
+            return Err(TeeReplayError::MissingNonce);
+        }
+        let stale = request.timestamp.abs_diff(now) > self.max_skew_ms.unsigned_abs()
+            || self.watermark.is_some_and(|mark| request.timestamp <= mark);
+        if stale {
+            return Err(TeeReplayError::StaleTimestamp {
+                timestamp: request.timestamp,
+                now,
+            });
+        }
+
+        self.expire(now);
+        if self.seen.contains(&request.nonce) {
+            return Err(TeeReplayError::DuplicateNonce(request.nonce.clone()));
+        }
+        if self.by_timestamp.len() >= self.capacity {
+            if let Some((timestamp, nonce)) = self.by_timestamp.pop_first() {
+                self.seen.remove(&nonce);
+                self.watermark = Some(self.watermark.map_or(timestamp, |m| m.max(timestamp)));
+            }
+        }
+        self.seen.insert(request.nonce.clone());
+        self.by_timestamp
+            .insert((request.timestamp, request.nonce.clone()));
+        Ok(())
+    }
+
+    /// Number of nonces currently remembered.
+    pub fn len(&self) -> usize {
+        self.by_timestamp.len()
+    }
+
+    /// Whether no nonces are currently remembered.
+    pub fn is_empty(&self) -> bool {
+        self.by_timestamp.is_empty()
+    }
+
+    /// Forget nonces whose timestamps can no longer pass the skew check.
+    fn expire(&mut self, now: i64) {
+        let cutoff = now.saturating_sub(self.max_skew_ms);
+        while self
+            .by_timestamp
+            .first()
+            .is_some_and(|(timestamp, _)| *timestamp < cutoff)
+        {
+            if let Some((_, nonce)) = self.by_timestamp.pop_first() {
+                self.seen.remove(&nonce);
+            }
+        }
+    }
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_SKEW, Self::DEFAULT_CAPACITY)
+    }
+}
+
 /// Types of TEE requests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -117,6 +235,113 @@ mod tests {
         assert!(matches!(parsed, TeeMessage::Request(_)));
     }
 
+    fn request_at(timestamp: i64, nonce: &str) -> TeeRequest {
+        TeeRequest {
+            timestamp,
+            nonce: nonce.to_string(),
+            ..TeeRequest::new("sess-1".into(), TeeRequestType::StoreSecret, vec![1])
+        }
+    }
+
+    #[test]
+    fn test_tee_request_nonces_are_unique() {
+        let a = TeeRequest::new("s".into(), TeeRequestType::RetrieveSecret, vec![]);
+        let b = TeeRequest::new("s".into(), TeeRequestType::RetrieveSecret, vec![]);
+        assert!(!a.nonce.is_empty());
+        assert_ne!(a.nonce, b.nonce);
+    }
+
+    #[test]
+    fn test_replay_guard_rejects_stale_timestamps() {
+        let mut guard = ReplayGuard::new(Duration::from_secs(30), 16);
+        let now = 1_700_000_000_000;
+
+        guard.check_at(&request_at(now - 29_000, "a"), now).unwrap();
+        assert_eq!(
+            guard.check_at(&request_at(now - 31_000, "b"), now),
+            Err(TeeReplayError::StaleTimestamp {
+                timestamp: now - 31_000,
+                now
+            })
+        );
+        assert!(matches!(
+            guard.check_at(&request_at(now + 31_000, "c"), now),
+            Err(TeeReplayError::StaleTimestamp { .. })
+        ));
+    }
+
+    #[test]
+    fn test_replay_guard_rejects_duplicate_and_missing_nonces() {
+        let mut guard = ReplayGuard::default();
+        let request = TeeRequest::new("sess-1".into(), TeeRequestType::StoreSecret, vec![1]);
+
+        guard.check(&request).unwrap();
+        assert_eq!(
+            guard.check(&request),
+            Err(TeeReplayError::DuplicateNonce(request.nonce.clone()))
+        );
+
+        let legacy: TeeRequest = serde_json::from_str(
+            r#"{"id":"r","session_id":"s","request_type":"retrieve_secret","payload":[],"timestamp":0}"#,
+        )
+        .unwrap();
+        assert_eq!(guard.check(&legacy), Err(TeeReplayError::MissingNonce));
+    }
+
+    #[test]
+    fn test_replay_guard_expires_and_bounds_its_cache() {
+        let mut guard = ReplayGuard::new(Duration::from_secs(30), 2);
+        let now = 1_700_000_000_000;
+
+        guard.check_at(&request_at(now, "a"), now).unwrap();
+        // After the window passes, the old nonce is forgotten.
+        guard
+            .check_at(&request_at(now + 60_000, "b"), now + 60_000)
+            .unwrap();
+        assert_eq!(guard.len(), 1);
+
+        let now = now + 60_000;
+        guard.check_at(&request_at(now + 1, "c"), now).unwrap();
+        guard.check_at(&request_at(now + 2, "d"), now).unwrap();
+        assert_eq!(guard.len(), 2);
+        // "b" was evicted for capacity; replaying it hits the watermark.
+        assert!(matches!(
+            guard.check_at(&request_at(now, "b"), now),
+            Err(TeeReplayError::StaleTimestamp { .. })
+        ));
+        guard.check_at(&request_at(now + 3, "e"), now).unwrap();
+    }
+
+    #[test]
+    fn test_replay_guard_orders_its_cache_by_timestamp() {
+        let mut guard = ReplayGuard::new(Duration::from_secs(30), 2);
+        let now = 1_700_000_000_000;
+
+        // A future-dated request inside the skew arrives first.
+        guard
+            .check_at(&request_at(now + 29_000, "future"), now)
+            .unwrap();
+        guard.check_at(&request_at(now, "a"), now).unwrap();
+        guard.check_at(&request_at(now + 1, "b"), now).unwrap();
+        // Eviction dropped "a", the earliest timestamp, not the earliest
+        // arrival, so honest requests just after it are still accepted.
+        guard.check_at(&request_at(now + 2, "c"), now).unwrap();
+        assert!(matches!(
+            guard.check_at(&request_at(now + 29_000, "future"), now),
+            Err(TeeReplayError::DuplicateNonce(_))
+        ));
+
+        // Expiry is not blocked by a future-dated entry at the front.
+        let mut guard = ReplayGuard::new(Duration::from_secs(30), 10);
+        guard
+            .check_at(&request_at(now + 29_000, "future"), now)
+            .unwrap();
+        guard.check_at(&request_at(now, "a"), now).unwrap();
+        let later = now + 31_000;
+        guard.check_at(&request_at(later, "b"), later).unwrap();
+        assert_eq!(guard.len(), 2, "\"a\" expired behind \"future\"");
+    }
+
     #[test]
     fn test_tee_response_success() {
         let resp = TeeResponse::success("req-1".into(), "sess-1".into(), vec![42]);
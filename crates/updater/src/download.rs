@@ -2,7 +2,7 @@
 
 use anyhow::{bail, Context};
 use sha2::{Digest, Sha256};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Cursor;
 use std::path::{Component, Path, PathBuf};
 use tempfile::TempDir;
@@ -11,8 +11,23 @@ const MAX_ARCHIVE_BYTES: u64 = 512 * 1024 * 1024;
 const MAX_EXTRACTED_BYTES: u64 = 4 * 1024 * 1024 * 1024;
 const MAX_EXTRACTED_ENTRIES: usize = 50_000;
 
+/// Download progress callback receiving `(bytes_downloaded, total_bytes)`.
+///
+/// `total_bytes` is `None` when the server sends no `Content-Length`; callers
+/// should render indeterminate progress in that case.
+pub type ProgressCallback = dyn Fn(u64, Option<u64>) + Send + Sync;
+
 /// Download one release asset into memory.
 pub async fn download_asset(url: &str) -> anyhow::Result<Vec<u8>> {
+    download_asset_with_progress(url, None).await
+}
+
+/// Download one release asset into memory, reporting progress after each
+/// received chunk.
+pub async fn download_asset_with_progress(
+    url: &str,
+    progress: Option<&ProgressCallback>,
+) -> anyhow::Result<Vec<u8>> {
     let client = reqwest::Client::builder()
         .user_agent("a3s-updater/0.3")
         .timeout(std::time::Duration::from_secs(300))
@@ -30,16 +45,11 @@ pub async fn download_asset(url: &str) -> anyhow::Result<Vec<u8>> {
             url
         );
     }
-    if response
-        .content_length()
-        .is_some_and(|size| size > MAX_ARCHIVE_BYTES)
-    {
+    let total = response.content_length();
+    if total.is_some_and(|size| size > MAX_ARCHIVE_BYTES) {
         bail!("release asset exceeds the {} byte limit", MAX_ARCHIVE_BYTES);
     }
-    let initial_capacity = response
-        .content_length()
-        .unwrap_or_default()
-        .min(MAX_ARCHIVE_BYTES) as usize;
+    let initial_capacity = total.unwrap_or_default().min(MAX_ARCHIVE_BYTES) as usize;
     let mut bytes = Vec::with_capacity(initial_capacity);
     while let Some(chunk) = response
         .chunk()
@@ -51,10 +61,33 @@ pub async fn download_asset(url: &str) -> anyhow::Result<Vec<u8>> {
             bail!("release asset exceeds the {} byte limit", MAX_ARCHIVE_BYTES);
         }
         bytes.extend_from_slice(&chunk);
+        if let Some(progress) = progress {
+            progress(bytes.len() as u64, total);
+        }
     }
     Ok(bytes)
 }
 
+/// Print download progress to stderr on a single, rewritten line.
+///
+/// Shows a percentage when the total size is known and a byte count
+/// otherwise. Suitable as an [`UpdateConfig::progress`](crate::UpdateConfig)
+/// callback for interactive terminals.
+pub fn print_download_progress(downloaded: u64, total: Option<u64>) {
+    use std::io::Write;
+
+    let mut stderr = std::io::stderr().lock();
+    let _ = match total {
+        Some(total) if total > 0 => write!(
+            stderr,
+            "\r  {:>3}% ({downloaded}/{total} bytes)",
+            downloaded.saturating_mul(100) / total
+        ),
+        _ => write!(stderr, "\r  {downloaded} bytes"),
+    };
+    let _ = stderr.flush();
+}
+
 /// Return a lowercase SHA-256 digest.
 pub fn sha256_hex(data: &[u8]) -> String {
     format!("{:x}", Sha256::digest(data))
@@ -77,6 +110,72 @@ pub fn verify_sha256(data: &[u8], expected: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse a `sha256sum`-style checksums file into `file name -> digest` pairs.
+///
+/// Each line is `<digest>  <name>`. A leading `*` (binary mode marker) on the
+/// name is ignored, as are blank lines and `#` comments. Entries are keyed by
+/// file name only, so `sha256sum dist/*` output (`./dist/a3s.tar.gz`) matches
+/// the release asset name.
+pub fn parse_sha256sums(text: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut sums = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((digest, name)) = line.split_once(char::is_whitespace) else {
+            bail!("malformed SHA256SUMS line {}", index + 1);
+        };
+        let path = name.trim_start().trim_start_matches('*');
+        let name = path.rsplit('/').next().unwrap_or(path);
+        if name.is_empty()
+            || digest.len() != 64
+            || !digest.bytes().all(|byte| byte.is_ascii_hexdigit())
+        {
+            bail!("malformed SHA256SUMS line {}", index + 1);
+        }
+        if sums
+            .insert(name.to_string(), digest.to_ascii_lowercase())
+            .is_some()
+        {
+            bail!("SHA256SUMS lists '{name}' more than once");
+        }
+    }
+    Ok(sums)
+}
+
+/// Verify a downloaded release against its `SHA256SUMS` entries.
+///
+/// The extracted binary and the archive are each checked when listed. A
+/// checksums file that covers neither is rejected rather than skipped.
+pub(crate) fn verify_release_checksums(
+    sums: &BTreeMap<String, String>,
+    archive_name: &str,
+    archive: &[u8],
+    binary: &Path,
+) -> anyhow::Result<()> {
+    let binary_name = binary
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid extracted binary path {}", binary.display()))?;
+    let archive_sum = sums.get(archive_name);
+    let binary_sum = sums.get(binary_name);
+    if archive_sum.is_none() && binary_sum.is_none() {
+        bail!("SHA256SUMS has no entry for '{binary_name}' or '{archive_name}'");
+    }
+    if let Some(expected) = archive_sum {
+        verify_sha256(archive, expected)
+            .with_context(|| format!("SHA256SUMS verification failed for '{archive_name}'"))?;
+    }
+    if let Some(expected) = binary_sum {
+        let bytes = std::fs::read(binary)
+            .with_context(|| format!("failed to read extracted binary {}", binary.display()))?;
+        verify_sha256(&bytes, expected)
+            .with_context(|| format!("SHA256SUMS verification failed for '{binary_name}'"))?;
+    }
+    Ok(())
+}
+
 /// Safely extract a complete gzip-compressed tar archive.
 ///
 /// Only directories and regular files are accepted. Links and special files
@@ -274,6 +373,53 @@ pub(crate) fn extract_release_binary(
 mod tests {
     use super::*;
 
+    /// Serve one canned HTTP response on a loopback port and return its URL.
+    fn serve_once(response: Vec<u8>) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0_u8; 1024];
+            let _ = stream.read(&mut request);
+            stream.write_all(&response).unwrap();
+        });
+        format!("http://{address}/asset.tar.gz")
+    }
+
+    async fn record_progress(url: &str) -> (Vec<u8>, Vec<(u64, Option<u64>)>) {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = events.clone();
+        let callback = move |downloaded: u64, total: Option<u64>| {
+            recorder.lock().unwrap().push((downloaded, total));
+        };
+        let bytes = download_asset_with_progress(url, Some(&callback))
+            .await
+            .unwrap();
+        let events = events.lock().unwrap().clone();
+        (bytes, events)
+    }
+
+    #[tokio::test]
+    async fn download_progress_reports_known_totals() {
+        let url = serve_once(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 7\r\nConnection: close\r\n\r\nfixture".to_vec(),
+        );
+        let (bytes, events) = record_progress(&url).await;
+        assert_eq!(bytes, b"fixture");
+        assert_eq!(events.last(), Some(&(7, Some(7))));
+        assert!(events.iter().all(|(_, total)| *total == Some(7)));
+    }
+
+    #[tokio::test]
+    async fn download_progress_is_indeterminate_without_content_length() {
+        let url = serve_once(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\nfixture".to_vec());
+        let (bytes, events) = record_progress(&url).await;
+        assert_eq!(bytes, b"fixture");
+        assert_eq!(events.last(), Some(&(7, None)));
+    }
+
     #[test]
     fn sha256_verification_accepts_prefix_and_rejects_mismatch() {
         let digest = sha256_hex(b"a3s");
@@ -283,6 +429,50 @@ mod tests {
         assert!(verify_sha256(b"a3s", "not-a-digest").is_err());
     }
 
+    #[test]
+    fn sha256sums_parsing_accepts_binary_markers_and_rejects_malformed_lines() {
+        let digest = sha256_hex(b"a3s");
+        let sums = parse_sha256sums(&format!(
+            "# release checksums\n{digest}  a3s-code.tar.gz\n\n{}  *a3s-code\n",
+            digest.to_ascii_uppercase()
+        ))
+        .unwrap();
+        assert_eq!(sums.len(), 2);
+        assert_eq!(sums["a3s-code.tar.gz"], digest);
+        assert_eq!(sums["a3s-code"], digest);
+
+        let sums = parse_sha256sums(&format!("{digest}  ./dist/a3s-code.tar.gz\n")).unwrap();
+        assert_eq!(sums["a3s-code.tar.gz"], digest);
+        assert!(parse_sha256sums(&format!("{digest}  dist/\n")).is_err());
+
+        assert!(parse_sha256sums("not-a-digest  a3s-code").is_err());
+        assert!(parse_sha256sums(&digest).is_err());
+        assert!(parse_sha256sums(&format!("{digest}  a3s\n{digest}  a3s\n")).is_err());
+    }
+
+    #[test]
+    fn release_checksums_must_cover_and_match_the_extracted_binary() {
+        let temp = tempfile::tempdir().unwrap();
+        let binary = temp.path().join("a3s-code");
+        std::fs::write(&binary, b"binary").unwrap();
+
+        let mut sums = BTreeMap::new();
+        assert!(verify_release_checksums(&sums, "a3s.tar.gz", b"archive", &binary).is_err());
+
+        sums.insert("a3s-code".to_string(), sha256_hex(b"binary"));
+        verify_release_checksums(&sums, "a3s.tar.gz", b"archive", &binary).unwrap();
+
+        sums.insert("a3s.tar.gz".to_string(), sha256_hex(b"tampered"));
+        let error = verify_release_checksums(&sums, "a3s.tar.gz", b"archive", &binary)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("a3s.tar.gz"), "{error}");
+
+        sums.remove("a3s.tar.gz");
+        std::fs::write(&binary, b"tampered").unwrap();
+        assert!(verify_release_checksums(&sums, "a3s.tar.gz", b"archive", &binary).is_err());
+    }
+
     #[test]
     fn full_archive_extraction_rejects_links() {
         let mut tar_bytes = Vec::new();
@@ -1,7 +1,13 @@
 //! GitHub Releases API client.
 
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
+use crate::platform::{arch_aliases, platform_libc, Libc};
+
+/// File name of the checksums asset published with each release.
+pub const CHECKSUMS_ASSET_NAME: &str = "SHA256SUMS";
+
 /// A GitHub release.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Release {
@@ -11,6 +17,44 @@ pub struct Release {
     pub body: Option<String>,
     /// Attached binary assets.
     pub assets: Vec<Asset>,
+    /// Whether GitHub marks this release as a pre-release.
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Whether the release is an unpublished draft. Drafts are only listed
+    /// for tokens with push access and are never eligible for updates.
+    #[serde(default)]
+    pub draft: bool,
+}
+
+/// Which releases the updater may move to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateChannel {
+    /// Full releases only; pre-release tags are ignored.
+    #[default]
+    Stable,
+    /// Full releases and pre-releases such as `v0.4.0-rc.1`, ordered by
+    /// semver precedence.
+    Prerelease,
+}
+
+impl UpdateChannel {
+    /// Whether `release` is eligible on this channel.
+    ///
+    /// Drafts are rejected on every channel. Stable also rejects both
+    /// GitHub-flagged pre-releases and tags carrying a semver pre-release
+    /// suffix, so a mislabelled rc never reaches users.
+    pub fn accepts(self, release: &Release) -> bool {
+        if release.draft {
+            return false;
+        }
+        let Ok(version) = parse_version(&release.tag_name) else {
+            return false;
+        };
+        match self {
+            Self::Stable => !release.prerelease && version.pre.is_empty(),
+            Self::Prerelease => true,
+        }
+    }
 }
 
 /// A single release asset (downloadable file).
@@ -25,9 +69,26 @@ pub struct Asset {
 }
 
 /// Fetch the latest release from a GitHub repository.
+///
+/// Requests to the public GitHub API authenticate with the `GITHUB_TOKEN`
+/// environment variable when it is set.
 pub async fn fetch_latest_release(owner: &str, repo: &str) -> anyhow::Result<Release> {
     let api_base = github_api_base();
-    fetch_release_url(&format!("{api_base}/repos/{owner}/{repo}/releases/latest")).await
+    let token = github_token(None, &api_base);
+    latest_release(&api_base, owner, repo, token.as_deref()).await
+}
+
+async fn latest_release(
+    api_base: &str,
+    owner: &str,
+    repo: &str,
+    token: Option<&str>,
+) -> anyhow::Result<Release> {
+    fetch_json(
+        &format!("{api_base}/repos/{owner}/{repo}/releases/latest"),
+        token,
+    )
+    .await
 }
 
 /// Fetch one release by semantic version, or the latest release when omitted.
@@ -36,42 +97,138 @@ pub async fn fetch_release(
     repo: &str,
     version: Option<&str>,
 ) -> anyhow::Result<Release> {
+    let api_base = github_api_base();
+    let token = github_token(None, &api_base);
     let Some(version) = version else {
-        return fetch_latest_release(owner, repo).await;
+        return latest_release(&api_base, owner, repo, token.as_deref()).await;
     };
     let version = version.trim().trim_start_matches('v');
     if version.is_empty() {
         return Err(anyhow::anyhow!("release version cannot be empty"));
     }
-    let api_base = github_api_base();
-    fetch_release_url(&format!(
-        "{api_base}/repos/{owner}/{repo}/releases/tags/v{version}"
-    ))
+    fetch_json(
+        &format!("{api_base}/repos/{owner}/{repo}/releases/tags/v{version}"),
+        token.as_deref(),
+    )
     .await
 }
 
+/// Fetch the newest release eligible on `channel`.
+///
+/// The stable channel uses GitHub's "latest release" endpoint. The pre-release
+/// channel lists recent releases and picks the highest semver version, since
+/// GitHub's "latest" never points at a pre-release.
+///
+/// `token` authenticates the requests and a blank value disables
+/// authentication. When `None`, the `GITHUB_TOKEN` environment variable is
+/// used, but only against the public GitHub API.
+pub async fn fetch_channel_release(
+    owner: &str,
+    repo: &str,
+    channel: UpdateChannel,
+    token: Option<&str>,
+) -> anyhow::Result<Release> {
+    let api_base = github_api_base();
+    let token = github_token(token, &api_base);
+    let token = token.as_deref();
+    match channel {
+        UpdateChannel::Stable => {
+            let release = latest_release(&api_base, owner, repo, token).await?;
+            if !channel.accepts(&release) {
+                return Err(anyhow::anyhow!(
+                    "latest release {} of {}/{} is not a stable release",
+                    release.tag_name,
+                    owner,
+                    repo
+                ));
+            }
+            Ok(release)
+        }
+        UpdateChannel::Prerelease => {
+            let releases: Vec<Release> = fetch_json(
+                &format!("{api_base}/repos/{owner}/{repo}/releases?per_page=30"),
+                token,
+            )
+            .await?;
+            select_newest_release(releases, channel).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "no releases found for {}/{} on the {:?} channel",
+                    owner,
+                    repo,
+                    channel
+                )
+            })
+        }
+    }
+}
+
+/// Pick the release with the highest semver version eligible on `channel`.
+pub fn select_newest_release(
+    releases: impl IntoIterator<Item = Release>,
+    channel: UpdateChannel,
+) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter(|release| channel.accepts(release))
+        .filter_map(|release| Some((parse_version(&release.tag_name).ok()?, release)))
+        .max_by(|(left, _), (right, _)| left.cmp(right))
+        .map(|(_, release)| release)
+}
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
 fn github_api_base() -> String {
     std::env::var("A3S_UPDATER_GITHUB_API_BASE")
-        .unwrap_or_else(|_| "https://api.github.com".to_string())
+        .unwrap_or_else(|_| GITHUB_API_BASE.to_string())
         .trim_end_matches('/')
         .to_string()
 }
 
-async fn fetch_release_url(url: &str) -> anyhow::Result<Release> {
+/// Pick the token to authenticate with against `api_base`.
+fn github_token(explicit: Option<&str>, api_base: &str) -> Option<String> {
+    select_token(explicit, api_base, std::env::var("GITHUB_TOKEN").ok())
+}
+
+/// An explicit token always wins, and a blank one disables authentication.
+/// Without one, the ambient `GITHUB_TOKEN` is only sent to the public GitHub
+/// API, so it never leaks to a custom `A3S_UPDATER_GITHUB_API_BASE` mirror.
+fn select_token(explicit: Option<&str>, api_base: &str, ambient: Option<String>) -> Option<String> {
+    match explicit {
+        Some(token) => Some(token.to_string()),
+        None if api_base == GITHUB_API_BASE => ambient,
+        None => None,
+    }
+    .map(|token| token.trim().to_string())
+    .filter(|token| !token.is_empty())
+}
+
+async fn fetch_json<T: DeserializeOwned>(url: &str, token: Option<&str>) -> anyhow::Result<T> {
     let client = reqwest::Client::builder()
         .user_agent("a3s-updater/0.1")
         .build()
         .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
 
-    let response = client
+    let mut request = client
         .get(url)
-        .header("Accept", "application/vnd.github+json")
+        .header("Accept", "application/vnd.github+json");
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
         .send()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to fetch release from {}: {}", url, e))?;
 
     if !response.status().is_success() {
         let status = response.status();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        if let Some(message) = rate_limit_message(status, response.headers(), now, token.is_some())
+        {
+            return Err(anyhow::anyhow!("{} ({})", message, url));
+        }
         let body = response.text().await.unwrap_or_default();
         return Err(anyhow::anyhow!(
             "GitHub API returned {} for {}: {}",
@@ -81,12 +238,53 @@ async fn fetch_release_url(url: &str) -> anyhow::Result<Release> {
         ));
     }
 
-    let release: Release = response
+    response
         .json()
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse GitHub release JSON: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Failed to parse GitHub release JSON: {}", e))
+}
 
-    Ok(release)
+/// Describe a GitHub rate-limit rejection, or `None` for other failures.
+///
+/// GitHub signals exhaustion with 403 or 429 and `X-RateLimit-Remaining: 0`;
+/// `X-RateLimit-Reset` carries the reset time in Unix seconds.
+fn rate_limit_message(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    now: u64,
+    authenticated: bool,
+) -> Option<String> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    let exhausted = header("x-ratelimit-remaining") == Some("0");
+    let limited = match status {
+        reqwest::StatusCode::TOO_MANY_REQUESTS => true,
+        reqwest::StatusCode::FORBIDDEN => exhausted,
+        _ => false,
+    };
+    if !limited {
+        return None;
+    }
+    let mut message = "GitHub API rate limit exceeded".to_string();
+    if let Some(reset) = header("x-ratelimit-reset").and_then(|value| value.parse::<u64>().ok()) {
+        let seconds = reset % 86_400;
+        let minutes = reset.saturating_sub(now).div_ceil(60);
+        message.push_str(&format!(
+            "; it resets at {:02}:{:02}:{:02} UTC (in {} min)",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60,
+            minutes
+        ));
+    }
+    if !authenticated {
+        message.push_str("; set GITHUB_TOKEN to authenticate and raise the limit");
+    }
+    Some(message)
 }
 
 /// Parse a version string, stripping an optional `v` prefix.
@@ -101,16 +299,70 @@ pub fn asset_name(binary_name: &str, version: &str, os: &str, arch: &str) -> Str
     format!("{}-{}-{}-{}.tar.gz", binary_name, version, os, arch)
 }
 
+/// Build the candidate asset file names for a platform, preferred first.
+///
+/// Linux targets honour `libc`: glibc hosts prefer the plain asset and fall
+/// back to a (static) `-musl` build, while musl hosts only accept `-musl`
+/// assets because glibc binaries cannot run there.
+pub fn asset_name_candidates(
+    binary_name: &str,
+    version: &str,
+    os: &str,
+    arch: &str,
+    libc: Option<Libc>,
+) -> Vec<String> {
+    let suffixes: &[&str] = match (os, libc) {
+        ("linux", Some(Libc::Glibc)) => &["", "-musl"],
+        ("linux", Some(Libc::Musl)) => &["-musl"],
+        _ => &[""],
+    };
+    let mut names = Vec::new();
+    for suffix in suffixes {
+        for alias in arch_aliases(arch) {
+            names.push(asset_name(
+                binary_name,
+                version,
+                os,
+                &format!("{alias}{suffix}"),
+            ));
+        }
+    }
+    names
+}
+
 /// Find the asset matching the current platform from a release's asset list.
+///
+/// Linux assets are selected for the running binary's C library; see
+/// [`find_matching_asset_for_libc`].
 pub fn find_matching_asset<'a>(
     release: &'a Release,
     binary_name: &str,
     os: &str,
     arch: &str,
+) -> Option<&'a Asset> {
+    find_matching_asset_for_libc(release, binary_name, os, arch, platform_libc())
+}
+
+/// Find the asset matching an explicit platform and C library.
+pub fn find_matching_asset_for_libc<'a>(
+    release: &'a Release,
+    binary_name: &str,
+    os: &str,
+    arch: &str,
+    libc: Option<Libc>,
 ) -> Option<&'a Asset> {
     let version = parse_version(&release.tag_name).ok()?;
-    let expected = asset_name(binary_name, &version.to_string(), os, arch);
-    release.assets.iter().find(|a| a.name == expected)
+    asset_name_candidates(binary_name, &version.to_string(), os, arch, libc)
+        .into_iter()
+        .find_map(|expected| release.assets.iter().find(|a| a.name == expected))
+}
+
+/// Find the `SHA256SUMS` checksums file published alongside a release.
+pub fn find_checksums_asset(release: &Release) -> Option<&Asset> {
+    release
+        .assets
+        .iter()
+        .find(|a| a.name == CHECKSUMS_ASSET_NAME)
 }
 
 /// Return a validated GitHub-provided SHA-256 digest for a release asset.
@@ -131,3 +383,75 @@ pub fn asset_sha256(asset: &Asset) -> anyhow::Result<String> {
     }
     Ok(digest.to_ascii_lowercase())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    fn rate_limit_headers(remaining: &str, reset: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-remaining",
+            HeaderValue::from_str(remaining).unwrap(),
+        );
+        headers.insert("x-ratelimit-reset", HeaderValue::from_str(reset).unwrap());
+        headers
+    }
+
+    #[test]
+    fn rate_limit_message_reports_reset_time_and_token_hint() {
+        // 1_700_000_000 is 22:13:20 UTC.
+        let headers = rate_limit_headers("0", "1700000000");
+        let message =
+            rate_limit_message(StatusCode::FORBIDDEN, &headers, 1_700_000_000 - 600, false)
+                .unwrap();
+        assert!(message.contains("rate limit exceeded"), "{message}");
+        assert!(message.contains("22:13:20 UTC (in 10 min)"), "{message}");
+        assert!(message.contains("GITHUB_TOKEN"), "{message}");
+
+        let message =
+            rate_limit_message(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), 0, true).unwrap();
+        assert!(!message.contains("GITHUB_TOKEN"), "{message}");
+    }
+
+    #[test]
+    fn forbidden_without_exhausted_quota_is_not_a_rate_limit() {
+        let headers = rate_limit_headers("42", "1700000000");
+        assert!(rate_limit_message(StatusCode::FORBIDDEN, &headers, 0, false).is_none());
+        assert!(rate_limit_message(StatusCode::NOT_FOUND, &HeaderMap::new(), 0, false).is_none());
+    }
+
+    #[test]
+    fn explicit_token_takes_precedence_and_blank_tokens_are_ignored() {
+        let ambient = || Some("env".to_string());
+        assert_eq!(
+            select_token(Some(" abc "), GITHUB_API_BASE, ambient()).as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            select_token(Some("  "), GITHUB_API_BASE, ambient()),
+            None,
+            "a blank explicit token disables authentication"
+        );
+        assert_eq!(
+            select_token(None, GITHUB_API_BASE, ambient()).as_deref(),
+            Some("env")
+        );
+    }
+
+    #[test]
+    fn ambient_token_is_not_sent_to_custom_api_bases() {
+        let ambient = || Some("env".to_string());
+        assert_eq!(select_token(None, "http://127.0.0.1:8080", ambient()), None);
+        assert_eq!(
+            select_token(None, "https://mirror.example.com", ambient()),
+            None
+        );
+        assert_eq!(
+            select_token(Some("explicit"), "http://127.0.0.1:8080", ambient()).as_deref(),
+            Some("explicit")
+        );
+    }
+}
@@ -7,9 +7,14 @@ use anyhow::Context;
 /// Replace the currently running binary with a new one.
 ///
 /// 1. Determines the current executable path.
-/// 2. Renames the current binary to `{name}.bak`.
-/// 3. Copies the new binary into place.
-/// 4. Sets executable permissions (unix).
+/// 2. Stages the new binary next to it, copying the current permissions and,
+///    where possible, ownership (unix).
+/// 3. Hard-links (or copies) the current binary to a hidden backup path,
+///    leaving the original in place.
+/// 4. Atomically renames the staged binary over the current one, so a crash
+///    at any point leaves either the old or the new binary at the path.
+/// 5. Restores the backup if any step after (3) fails, and removes it only
+///    once the replacement is durable.
 ///
 /// Note: The caller is responsible for cleaning up the temp directory
 /// that contains `new_binary_path` (e.g. by dropping a `TempDir`).
@@ -26,6 +31,21 @@ pub fn replace_binary(new_binary_path: &Path) -> anyhow::Result<()> {
 }
 
 fn replace_binary_at(new_binary_path: &Path, current_exe: &Path) -> anyhow::Result<()> {
+    replace_binary_with(new_binary_path, current_exe, |staging, target| {
+        staging
+            .persist(target)
+            .map(drop)
+            .map_err(|error| error.error)
+    })
+}
+
+/// Replacement flow with the final activation step injected, so tests can
+/// simulate a failed swap after the backup has been made.
+fn replace_binary_with(
+    new_binary_path: &Path,
+    current_exe: &Path,
+    activate: impl FnOnce(tempfile::NamedTempFile, &Path) -> std::io::Result<()>,
+) -> anyhow::Result<()> {
     if !new_binary_path.is_file() {
         return Err(anyhow::anyhow!(
             "new binary does not exist: {}",
@@ -35,6 +55,8 @@ fn replace_binary_at(new_binary_path: &Path, current_exe: &Path) -> anyhow::Resu
     let parent = current_exe
         .parent()
         .context("current executable has no parent directory")?;
+    let current_metadata = std::fs::metadata(current_exe)
+        .with_context(|| format!("failed to inspect {}", current_exe.display()))?;
     let staging = tempfile::NamedTempFile::new_in(parent).with_context(|| {
         format!(
             "failed to create update staging file in {}",
@@ -48,58 +70,89 @@ fn replace_binary_at(new_binary_path: &Path, current_exe: &Path) -> anyhow::Resu
             parent.display()
         )
     })?;
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-
-        std::fs::set_permissions(staging.path(), std::fs::Permissions::from_mode(0o755))
-            .with_context(|| {
-                format!(
-                    "failed to set executable permissions on {}",
-                    staging.path().display()
-                )
-            })?;
-    }
+    copy_executable_metadata(&current_metadata, staging.path())?;
     staging
         .as_file()
         .sync_all()
         .with_context(|| format!("failed to sync staged binary in {}", parent.display()))?;
 
     let backup = unique_backup_path(current_exe);
-    std::fs::rename(current_exe, &backup).with_context(|| {
-        format!(
-            "failed to move current binary {} to {}",
-            current_exe.display(),
-            backup.display()
-        )
-    })?;
-    match staging.persist(current_exe) {
-        Ok(_) => {
+    std::fs::hard_link(current_exe, &backup)
+        .or_else(|_| std::fs::copy(current_exe, &backup).map(drop))
+        .with_context(|| {
+            format!(
+                "failed to back up current binary {} to {}",
+                current_exe.display(),
+                backup.display()
+            )
+        })?;
+    let activated = activate(staging, current_exe)
+        .with_context(|| {
+            format!(
+                "failed to atomically activate staged binary at {}",
+                current_exe.display()
+            )
+        })
+        .and_then(|()| sync_directory(parent));
+    match activated {
+        Ok(()) => {
             let _ = std::fs::remove_file(&backup);
-            sync_directory(parent)?;
             Ok(())
         }
         Err(error) => {
-            let restore = std::fs::rename(&backup, current_exe);
-            if let Err(restore_error) = restore {
-                return Err(anyhow::anyhow!(
-                    "failed to activate staged binary at {}: {}; failed to restore backup {}: {}",
-                    current_exe.display(),
-                    error.error,
+            // `rename` replaces a partially activated binary atomically, so
+            // the original is back in place whichever step failed. When the
+            // swap never happened, the backup is a hard link to the current
+            // binary and `rename` leaves both names, so drop the spare one.
+            if let Err(restore_error) = std::fs::rename(&backup, current_exe) {
+                return Err(error.context(format!(
+                    "failed to restore backup {} to {}: {}",
                     backup.display(),
+                    current_exe.display(),
                     restore_error
-                ));
+                )));
             }
-            Err(error.error).with_context(|| {
-                format!(
-                    "failed to atomically activate staged binary at {}",
-                    current_exe.display()
-                )
-            })
+            let _ = std::fs::remove_file(&backup);
+            Err(error)
         }
     }
 }
 
+/// Carry the current binary's permission bits and ownership over to the
+/// staged replacement. Ownership changes need privileges, so they are
+/// best-effort; the executable bit is always ensured.
+///
+/// Ownership is set first because `chown` clears setuid/setgid. Those bits
+/// are only carried over when the staged file ends up with the original
+/// owner, so a failed `chown` never leaves a setuid binary owned by the
+/// invoking user.
+#[cfg(unix)]
+fn copy_executable_metadata(current: &std::fs::Metadata, staged: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let staged_metadata = std::fs::metadata(staged)
+        .with_context(|| format!("failed to inspect {}", staged.display()))?;
+    let same_owner =
+        staged_metadata.uid() == current.uid() && staged_metadata.gid() == current.gid();
+    let owned = same_owner
+        || std::os::unix::fs::chown(staged, Some(current.uid()), Some(current.gid())).is_ok();
+
+    let mask = if owned { 0o7777 } else { 0o777 };
+    let mode = current.permissions().mode() & mask;
+    let mode = if mode & 0o111 == 0 { 0o755 } else { mode };
+    std::fs::set_permissions(staged, std::fs::Permissions::from_mode(mode)).with_context(|| {
+        format!(
+            "failed to set executable permissions on {}",
+            staged.display()
+        )
+    })
+}
+
+#[cfg(not(unix))]
+fn copy_executable_metadata(_current: &std::fs::Metadata, _staged: &Path) -> anyhow::Result<()> {
+    Ok(())
+}
+
 fn unique_backup_path(current_exe: &Path) -> PathBuf {
     let parent = current_exe.parent().unwrap_or_else(|| Path::new("."));
     let name = current_exe
@@ -160,4 +213,71 @@ mod tests {
 
         assert_eq!(std::fs::read(&current).unwrap(), b"old");
     }
+
+    #[test]
+    fn failed_swap_restores_the_original_binary() {
+        let temp = tempfile::tempdir().unwrap();
+        let current = temp.path().join("a3s");
+        let replacement = temp.path().join("replacement");
+        std::fs::write(&current, b"old").unwrap();
+        std::fs::write(&replacement, b"new").unwrap();
+
+        let error = replace_binary_with(&replacement, &current, |_staging, target| {
+            // The original never leaves its path while the swap is pending.
+            assert_eq!(std::fs::read(target).unwrap(), b"old");
+            Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "simulated swap failure",
+            ))
+        })
+        .unwrap_err();
+
+        assert!(format!("{error:#}").contains("simulated swap failure"));
+        assert_eq!(std::fs::read(&current).unwrap(), b"old");
+        let mut entries: Vec<_> = temp
+            .path()
+            .read_dir()
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["a3s", "replacement"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn replacement_preserves_the_current_permission_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let current = temp.path().join("a3s");
+        let replacement = temp.path().join("replacement");
+        std::fs::write(&current, b"old").unwrap();
+        std::fs::set_permissions(&current, std::fs::Permissions::from_mode(0o750)).unwrap();
+        std::fs::write(&replacement, b"new").unwrap();
+
+        replace_binary_at(&replacement, &current).unwrap();
+
+        let mode = std::fs::metadata(&current).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn replacement_keeps_setuid_when_ownership_matches() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let current = temp.path().join("a3s");
+        let replacement = temp.path().join("replacement");
+        std::fs::write(&current, b"old").unwrap();
+        std::fs::set_permissions(&current, std::fs::Permissions::from_mode(0o4755)).unwrap();
+        std::fs::write(&replacement, b"new").unwrap();
+
+        replace_binary_at(&replacement, &current).unwrap();
+
+        let mode = std::fs::metadata(&current).unwrap().permissions().mode();
+        assert_eq!(mode & 0o7777, 0o4755);
+    }
 }
@@ -2,7 +2,8 @@
 //!
 //! Each binary provides an [`UpdateConfig`] describing itself, then calls
 //! [`run_update`] to check for a newer release, download the matching
-//! platform asset, and replace the running binary in-place.
+//! platform asset, and replace the running binary in-place. [`check_update`]
+//! performs only the comparison, for "update available" hints.
 
 mod component;
 mod download;
@@ -17,8 +18,9 @@ pub use component::{
     RECEIPT_SCHEMA_VERSION,
 };
 pub use download::{
-    download_asset, extract_release_archive, extract_tar_gz_archive, extract_zip_archive,
-    sha256_hex, verify_sha256,
+    download_asset, download_asset_with_progress, extract_release_archive, extract_tar_gz_archive,
+    extract_zip_archive, parse_sha256sums, print_download_progress, sha256_hex, verify_sha256,
+    ProgressCallback,
 };
 pub use fleet::{
     FleetUpdater, FleetUpgradeHost, ManagedComponent, ProtocolRange, ReleaseChannel,
@@ -27,9 +29,11 @@ pub use fleet::{
     SIGNED_TARGET_SCHEMA_VERSION,
 };
 pub use github::{
-    asset_sha256, fetch_latest_release, fetch_release, find_matching_asset, parse_version, Asset,
-    Release,
+    asset_name_candidates, asset_sha256, fetch_channel_release, fetch_latest_release,
+    fetch_release, find_checksums_asset, find_matching_asset, find_matching_asset_for_libc,
+    parse_version, select_newest_release, Asset, Release, UpdateChannel, CHECKSUMS_ASSET_NAME,
 };
+pub use platform::{platform_libc, platform_target, Libc};
 pub use systemd::{activate_systemd_unit, stage_systemd_unit, SystemdUnitSpec};
 
 /// Configuration for the update check — each binary provides its own.
@@ -44,32 +48,82 @@ pub struct UpdateConfig {
     pub github_owner: &'static str,
     /// GitHub repository name (e.g. `"Code"`).
     pub github_repo: &'static str,
+    /// GitHub token sent as a bearer `Authorization` header to avoid the
+    /// shared unauthenticated rate limit; a blank value disables
+    /// authentication. When `None`, `GITHUB_TOKEN` is used against the public
+    /// GitHub API only, never against a custom `A3S_UPDATER_GITHUB_API_BASE`.
+    pub github_token: Option<String>,
+    /// Release channel to follow; [`UpdateChannel::Stable`] ignores pre-releases.
+    pub channel: UpdateChannel,
+    /// Skip verifying the extracted binary against the release `SHA256SUMS`
+    /// asset. The GitHub asset digest is still checked.
+    pub skip_checksum_verification: bool,
+    /// Optional download progress callback. `None` keeps the download quiet;
+    /// pass [`print_download_progress`] for a percentage on stderr.
+    pub progress: Option<Box<ProgressCallback>>,
+}
+
+impl UpdateConfig {
+    /// Create a config with safe defaults: the stable channel, checksum
+    /// verification on, no explicit token, and no progress callback.
+    ///
+    /// Prefer this over a struct literal so new optional fields do not break
+    /// callers; adjust the public fields afterwards as needed.
+    pub fn new(
+        binary_name: &'static str,
+        crate_name: &'static str,
+        current_version: &'static str,
+        github_owner: &'static str,
+        github_repo: &'static str,
+    ) -> Self {
+        Self {
+            binary_name,
+            crate_name,
+            current_version,
+            github_owner,
+            github_repo,
+            github_token: None,
+            channel: UpdateChannel::Stable,
+            skip_checksum_verification: false,
+            progress: None,
+        }
+    }
+}
+
+/// Check whether a newer release is available without downloading it.
+///
+/// Returns the newest [`Release`] on the configured channel when it is newer
+/// than `config.current_version` and ships an asset for this platform — that
+/// is, when [`run_update`] would install something. Nothing is printed, so
+/// callers can use this for a non-blocking "update available" hint.
+pub async fn check_update(config: &UpdateConfig) -> anyhow::Result<Option<Release>> {
+    let check = UpdateCheck::fetch(config).await?;
+    let installable = check.is_newer() && check.asset(config.binary_name).is_some();
+    Ok(installable.then_some(check.release))
 }
 
 /// Run the full update flow: check -> download -> replace.
 pub async fn run_update(config: &UpdateConfig) -> anyhow::Result<()> {
     println!("Checking for updates...");
 
-    let (os, arch) = platform::platform_target()?;
-    let release = github::fetch_latest_release(config.github_owner, config.github_repo).await?;
-    let latest_version = github::parse_version(&release.tag_name)?;
-    let current_version = semver::Version::parse(config.current_version).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to parse current version '{}': {}",
-            config.current_version,
-            e
-        )
-    })?;
+    let check = UpdateCheck::fetch(config).await?;
+    let UpdateCheck {
+        os,
+        arch,
+        current_version,
+        latest_version,
+        release,
+    } = &check;
 
     println!("Current version: {}", current_version);
     println!("Latest version:  {}", latest_version);
 
-    if current_version >= latest_version {
+    if !check.is_newer() {
         println!("\nAlready up to date (v{}).", current_version);
         return Ok(());
     }
 
-    let asset = match github::find_matching_asset(&release, config.binary_name, &os, &arch) {
+    let asset = match check.asset(config.binary_name) {
         Some(a) => a,
         None => {
             println!("\nNo pre-built binary found for {}-{}.", os, arch);
@@ -78,17 +132,42 @@ pub async fn run_update(config: &UpdateConfig) -> anyhow::Result<()> {
         }
     };
 
+    let checksums = if config.skip_checksum_verification {
+        None
+    } else {
+        let sums_asset = github::find_checksums_asset(release).ok_or_else(|| {
+            anyhow::anyhow!(
+                "release {} has no {} asset; refusing to install an unverified binary",
+                release.tag_name,
+                github::CHECKSUMS_ASSET_NAME
+            )
+        })?;
+        let sums_checksum = github::asset_sha256(sums_asset)?;
+        let bytes = download::download_asset(&sums_asset.browser_download_url).await?;
+        download::verify_sha256(&bytes, &sums_checksum)?;
+        let text = String::from_utf8(bytes)
+            .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", github::CHECKSUMS_ASSET_NAME))?;
+        Some(download::parse_sha256sums(&text)?)
+    };
+
     println!(
         "\nDownloading {} v{} ({}-{})...",
         config.binary_name, latest_version, os, arch
     );
 
     let checksum = github::asset_sha256(asset)?;
-    let archive = download::download_asset(&asset.browser_download_url).await?;
+    let archive = download::download_asset_with_progress(
+        &asset.browser_download_url,
+        config.progress.as_deref(),
+    )
+    .await?;
     download::verify_sha256(&archive, &checksum)?;
     let (new_binary, _temp_dir) =
         download::extract_release_binary(&archive, &asset.name, config.binary_name)?;
-    verify_downloaded_version(&new_binary, &latest_version)?;
+    if let Some(sums) = &checksums {
+        download::verify_release_checksums(sums, &asset.name, &archive, &new_binary)?;
+    }
+    verify_downloaded_version(&new_binary, latest_version)?;
     install::replace_binary(&new_binary)?;
     // _temp_dir is dropped here, automatically cleaning up the temp directory
 
@@ -100,6 +179,51 @@ pub async fn run_update(config: &UpdateConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Comparison between the running binary and the newest eligible release.
+struct UpdateCheck {
+    os: String,
+    arch: String,
+    current_version: semver::Version,
+    latest_version: semver::Version,
+    release: Release,
+}
+
+impl UpdateCheck {
+    async fn fetch(config: &UpdateConfig) -> anyhow::Result<Self> {
+        let (os, arch) = platform::platform_target()?;
+        let release = github::fetch_channel_release(
+            config.github_owner,
+            config.github_repo,
+            config.channel,
+            config.github_token.as_deref(),
+        )
+        .await?;
+        let latest_version = github::parse_version(&release.tag_name)?;
+        let current_version = semver::Version::parse(config.current_version).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to parse current version '{}': {}",
+                config.current_version,
+                e
+            )
+        })?;
+        Ok(Self {
+            os,
+            arch,
+            current_version,
+            latest_version,
+            release,
+        })
+    }
+
+    fn is_newer(&self) -> bool {
+        self.latest_version > self.current_version
+    }
+
+    fn asset(&self, binary_name: &str) -> Option<&Asset> {
+        github::find_matching_asset(&self.release, binary_name, &self.os, &self.arch)
+    }
+}
+
 fn verify_downloaded_version(
     binary: &std::path::Path,
     expected: &semver::Version,
@@ -183,6 +307,86 @@ mod tests {
         assert_eq!(name, "a3s-code-0.3.0-darwin-arm64.tar.gz");
     }
 
+    #[test]
+    fn test_asset_name_candidates_cover_aarch64_and_libc() {
+        assert_eq!(
+            asset_name_candidates("a3s", "0.3.0", "linux", "arm64", Some(Libc::Musl)),
+            [
+                "a3s-0.3.0-linux-arm64-musl.tar.gz",
+                "a3s-0.3.0-linux-aarch64-musl.tar.gz"
+            ]
+        );
+        assert_eq!(
+            asset_name_candidates("a3s", "0.3.0", "linux", "x86_64", Some(Libc::Glibc)),
+            [
+                "a3s-0.3.0-linux-x86_64.tar.gz",
+                "a3s-0.3.0-linux-x86_64-musl.tar.gz"
+            ]
+        );
+        assert_eq!(
+            asset_name_candidates("a3s", "0.3.0", "darwin", "arm64", None),
+            [
+                "a3s-0.3.0-darwin-arm64.tar.gz",
+                "a3s-0.3.0-darwin-aarch64.tar.gz"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_matching_asset_selects_libc_variant() {
+        let asset = |name: &str| github::Asset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+            digest: None,
+        };
+        let release = Release {
+            tag_name: "v0.3.0".to_string(),
+            body: None,
+            prerelease: false,
+            draft: false,
+            assets: vec![
+                asset("a3s-code-0.3.0-linux-aarch64.tar.gz"),
+                asset("a3s-code-0.3.0-linux-aarch64-musl.tar.gz"),
+            ],
+        };
+
+        let musl =
+            find_matching_asset_for_libc(&release, "a3s-code", "linux", "arm64", Some(Libc::Musl))
+                .unwrap();
+        assert_eq!(musl.name, "a3s-code-0.3.0-linux-aarch64-musl.tar.gz");
+        let glibc =
+            find_matching_asset_for_libc(&release, "a3s-code", "linux", "arm64", Some(Libc::Glibc))
+                .unwrap();
+        assert_eq!(glibc.name, "a3s-code-0.3.0-linux-aarch64.tar.gz");
+
+        let musl_only = Release {
+            assets: vec![asset("a3s-code-0.3.0-linux-aarch64-musl.tar.gz")],
+            ..release.clone()
+        };
+        let fallback = find_matching_asset_for_libc(
+            &musl_only,
+            "a3s-code",
+            "linux",
+            "arm64",
+            Some(Libc::Glibc),
+        )
+        .unwrap();
+        assert_eq!(fallback.name, "a3s-code-0.3.0-linux-aarch64-musl.tar.gz");
+
+        let glibc_only = Release {
+            assets: vec![asset("a3s-code-0.3.0-linux-aarch64.tar.gz")],
+            ..release
+        };
+        assert!(find_matching_asset_for_libc(
+            &glibc_only,
+            "a3s-code",
+            "linux",
+            "arm64",
+            Some(Libc::Musl)
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_version_compare_newer() {
         let current = semver::Version::parse("0.1.0").unwrap();
@@ -204,6 +408,93 @@ mod tests {
         assert!(current >= latest, "0.3.0 should be >= 0.2.0");
     }
 
+    #[test]
+    fn test_version_compare_prerelease() {
+        let rc = github::parse_version("v0.4.0-rc.1").unwrap();
+        let stable = github::parse_version("v0.4.0").unwrap();
+        let older = github::parse_version("v0.3.0").unwrap();
+        assert!(rc < stable, "0.4.0-rc.1 should be less than 0.4.0");
+        assert!(rc > older, "0.4.0-rc.1 should be greater than 0.3.0");
+    }
+
+    #[test]
+    fn test_update_config_new_uses_safe_defaults() {
+        let config = UpdateConfig::new("a3s", "a3s", "0.3.0", "A3S-Lab", "a3s");
+        assert_eq!(config.channel, UpdateChannel::Stable);
+        assert!(!config.skip_checksum_verification);
+        assert!(config.github_token.is_none());
+        assert!(config.progress.is_none());
+    }
+
+    #[test]
+    fn test_channel_release_selection() {
+        let release = |tag: &str, prerelease: bool| Release {
+            tag_name: tag.to_string(),
+            body: None,
+            assets: Vec::new(),
+            prerelease,
+            draft: false,
+        };
+        let releases = vec![
+            release("v0.3.0", false),
+            release("v0.4.0-rc.1", true),
+            release("v0.4.0-rc.2", false),
+            release("nightly", true),
+        ];
+
+        let stable = select_newest_release(releases.clone(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "v0.3.0");
+        let pre = select_newest_release(releases.clone(), UpdateChannel::Prerelease).unwrap();
+        assert_eq!(pre.tag_name, "v0.4.0-rc.2");
+
+        let mut with_final = releases;
+        with_final.push(release("v0.4.0", false));
+        let pre = select_newest_release(with_final.clone(), UpdateChannel::Prerelease).unwrap();
+        assert_eq!(pre.tag_name, "v0.4.0");
+
+        let mut with_draft = with_final;
+        with_draft.push(Release {
+            draft: true,
+            ..release("v0.5.0", false)
+        });
+        let stable = select_newest_release(with_draft.clone(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "v0.4.0");
+        let pre = select_newest_release(with_draft, UpdateChannel::Prerelease).unwrap();
+        assert_eq!(pre.tag_name, "v0.4.0");
+    }
+
+    #[test]
+    fn test_update_check_requires_newer_version_and_platform_asset() {
+        let check = |current: &str, arch: &str| UpdateCheck {
+            os: "linux".to_string(),
+            arch: arch.to_string(),
+            current_version: semver::Version::parse(current).unwrap(),
+            latest_version: semver::Version::parse("0.3.0").unwrap(),
+            release: Release {
+                tag_name: "v0.3.0".to_string(),
+                body: None,
+                assets: vec![github::Asset {
+                    name: "a3s-code-0.3.0-linux-x86_64.tar.gz".to_string(),
+                    browser_download_url: "https://example.com/linux-x86_64.tar.gz".to_string(),
+                    digest: None,
+                }],
+                prerelease: false,
+                draft: false,
+            },
+        };
+
+        let newer = check("0.2.0", "x86_64");
+        assert!(newer.is_newer());
+        assert!(newer.asset("a3s-code").is_some());
+
+        let current = check("0.3.0", "x86_64");
+        assert!(!current.is_newer());
+
+        let unsupported = check("0.2.0", "riscv64");
+        assert!(unsupported.is_newer());
+        assert!(unsupported.asset("a3s-code").is_none());
+    }
+
     #[test]
     fn test_parse_github_release_json() {
         let json = serde_json::json!({
@@ -225,6 +516,7 @@ mod tests {
         assert_eq!(release.tag_name, "v0.3.0");
         assert_eq!(release.body.as_deref(), Some("Bug fixes and improvements"));
         assert_eq!(release.assets.len(), 2);
+        assert!(!release.prerelease);
         assert_eq!(release.assets[0].name, "a3s-code-0.3.0-darwin-arm64.tar.gz");
     }
 
@@ -233,6 +525,8 @@ mod tests {
         let release = Release {
             tag_name: "v0.3.0".to_string(),
             body: None,
+            prerelease: false,
+            draft: false,
             assets: vec![
                 github::Asset {
                     name: "a3s-code-0.3.0-darwin-arm64.tar.gz".to_string(),
@@ -262,6 +556,30 @@ mod tests {
         assert!(found.is_none());
     }
 
+    #[test]
+    fn test_find_checksums_asset() {
+        let mut release = Release {
+            tag_name: "v0.3.0".to_string(),
+            body: None,
+            prerelease: false,
+            draft: false,
+            assets: vec![github::Asset {
+                name: "a3s-code-0.3.0-linux-x86_64.tar.gz".to_string(),
+                browser_download_url: "https://example.com/linux-x86_64.tar.gz".to_string(),
+                digest: None,
+            }],
+        };
+        assert!(find_checksums_asset(&release).is_none());
+
+        release.assets.push(github::Asset {
+            name: CHECKSUMS_ASSET_NAME.to_string(),
+            browser_download_url: "https://example.com/SHA256SUMS".to_string(),
+            digest: None,
+        });
+        let found = find_checksums_asset(&release).unwrap();
+        assert_eq!(found.browser_download_url, "https://example.com/SHA256SUMS");
+    }
+
     #[test]
     fn test_strip_version_prefix() {
         let v1 = github::parse_version("v0.2.0").unwrap();
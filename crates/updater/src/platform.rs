@@ -20,3 +20,39 @@ pub fn platform_target() -> anyhow::Result<(String, String)> {
 
     Ok((os.to_string(), arch.to_string()))
 }
+
+/// C library flavour of a Linux release asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Libc {
+    /// glibc; assets carry no libc suffix.
+    Glibc,
+    /// musl; assets carry a `-musl` suffix.
+    Musl,
+}
+
+/// Detect the C library the running binary uses, or `None` off Linux.
+///
+/// The running binary is the best evidence available: a glibc build can only
+/// run where glibc is present, and a musl build is what the user chose to
+/// install, so the replacement keeps the same flavour.
+pub fn platform_libc() -> Option<Libc> {
+    if std::env::consts::OS != "linux" {
+        return None;
+    }
+    if cfg!(target_env = "musl") {
+        Some(Libc::Musl)
+    } else {
+        Some(Libc::Glibc)
+    }
+}
+
+/// Architecture spellings accepted in asset names, preferred first.
+///
+/// Release pipelines disagree on `arm64` versus `aarch64`, so both are
+/// treated as the same target.
+pub fn arch_aliases(arch: &str) -> Vec<&str> {
+    match arch {
+        "arm64" | "aarch64" => vec!["arm64", "aarch64"],
+        other => vec![other],
+    }
+}